@@ -0,0 +1,277 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, space0, space1},
+    combinator::map,
+    error::{context, ParseError},
+    multi::{count, many0, many1},
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::helpers::{indent, integer, string};
+use crate::types::{
+    ArithmeticOperator, AssignmentOperator, ComparisonOperator, Condition, ConditionExpr,
+};
+
+fn factor<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, ConditionExpr<'a>, E> {
+    alt((
+        map(integer, ConditionExpr::Number),
+        delimited(
+            tuple((char('('), space0)),
+            expression,
+            tuple((space0, char(')'))),
+        ),
+        map(string, ConditionExpr::Condition),
+    ))(input)
+}
+
+fn fold_operations<'a>(
+    first: ConditionExpr<'a>,
+    rest: Vec<(ArithmeticOperator, ConditionExpr<'a>)>,
+) -> ConditionExpr<'a> {
+    rest.into_iter().fold(first, |left, (operator, right)| {
+        ConditionExpr::Operation(Box::new(left), operator, Box::new(right))
+    })
+}
+
+fn term<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, ConditionExpr<'a>, E> {
+    let (input, (first, rest)) = tuple((
+        factor,
+        many0(tuple((
+            delimited(
+                space1,
+                alt((
+                    map(char('*'), |_| ArithmeticOperator::Multiply),
+                    map(char('/'), |_| ArithmeticOperator::Divide),
+                    map(char('%'), |_| ArithmeticOperator::Modulo),
+                )),
+                space1,
+            ),
+            factor,
+        ))),
+    ))(input)?;
+    Ok((input, fold_operations(first, rest)))
+}
+
+/// parse an arithmetic expression, with `*`, `/` and `%` having priority over `+` and `-`
+pub fn expression<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, ConditionExpr<'a>, E> {
+    let (input, (first, rest)) = context(
+        "condition expression",
+        tuple((
+            term,
+            many0(tuple((
+                delimited(
+                    space1,
+                    alt((
+                        map(char('+'), |_| ArithmeticOperator::Add),
+                        map(char('-'), |_| ArithmeticOperator::Subtract),
+                    )),
+                    space1,
+                ),
+                term,
+            ))),
+        )),
+    )(input)?;
+    Ok((input, fold_operations(first, rest)))
+}
+
+fn comparison_operator<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, ComparisonOperator, E> {
+    alt((
+        map(tag("=="), |_| ComparisonOperator::Equal),
+        map(tag("!="), |_| ComparisonOperator::NotEqual),
+        map(tag("<="), |_| ComparisonOperator::LessOrEqual),
+        map(tag(">="), |_| ComparisonOperator::GreaterOrEqual),
+        map(tag("<"), |_| ComparisonOperator::Less),
+        map(tag(">"), |_| ComparisonOperator::Greater),
+    ))(input)
+}
+
+fn assignment_operator<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, AssignmentOperator, E> {
+    alt((
+        map(tag("<?="), |_| AssignmentOperator::Min),
+        map(tag(">?="), |_| AssignmentOperator::Max),
+        map(tag("+="), |_| AssignmentOperator::Add),
+        map(tag("-="), |_| AssignmentOperator::Subtract),
+        map(tag("*="), |_| AssignmentOperator::Multiply),
+        map(tag("/="), |_| AssignmentOperator::Divide),
+        map(tag("="), |_| AssignmentOperator::Set),
+    ))(input)
+}
+
+fn keyword_with_name<'a, E: ParseError<&'a str>>(
+    keyword: &'static str,
+) -> impl Fn(&'a str) -> IResult<&'a str, &'a str, E> {
+    move |input| preceded(tuple((tag(keyword), space1)), string)(input)
+}
+
+fn single_line_condition<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Condition<'a>, E> {
+    alt((
+        map(tag("never"), |_| Condition::Never),
+        map(keyword_with_name("has"), Condition::Has),
+        map(keyword_with_name("not"), Condition::Not),
+        map(keyword_with_name("set"), Condition::Set),
+        map(keyword_with_name("clear"), Condition::Clear),
+        map(terminated(string, tuple((space1, tag("++")))), |name| {
+            Condition::Increment(name)
+        }),
+        map(terminated(string, tuple((space1, tag("--")))), |name| {
+            Condition::Decrement(name)
+        }),
+        map(
+            tuple((string, space1, assignment_operator, space1, expression)),
+            |(name, _, operator, _, value)| Condition::Assignment {
+                name,
+                operator,
+                value,
+            },
+        ),
+        map(
+            tuple((expression, space1, comparison_operator, space1, expression)),
+            |(left, _, operator, _, right)| Condition::Comparison {
+                left,
+                operator,
+                right,
+            },
+        ),
+    ))(input)
+}
+
+/// parse a condition on a line with the given indentation, and it's sub conditions for `and` and `or` blocks
+pub fn parse_condition<'a, E: ParseError<&'a str>>(
+    indent_level: usize,
+    input: &'a str,
+) -> IResult<&'a str, Condition<'a>, E> {
+    let (input, _) = count(indent, indent_level)(input)?;
+    context(
+        "condition",
+        alt((
+            map(
+                preceded(
+                    tuple((tag("and"), space0, line_ending)),
+                    many1(|input| parse_condition(indent_level + 1, input)),
+                ),
+                Condition::And,
+            ),
+            map(
+                preceded(
+                    tuple((tag("or"), space0, line_ending)),
+                    many1(|input| parse_condition(indent_level + 1, input)),
+                ),
+                Condition::Or,
+            ),
+            terminated(single_line_condition, tuple((space0, line_ending))),
+        )),
+    )(input)
+}
+
+/// parse a list of conditions, one per line with the given indentation
+pub fn parse_conditions<'a, E: ParseError<&'a str>>(
+    indent_level: usize,
+    input: &'a str,
+) -> IResult<&'a str, Vec<Condition<'a>>, E> {
+    many1(|input| parse_condition(indent_level, input))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_conditions() {
+        let data = r#""combat rating" > 100
+has "event: foo"
+not "x"
+never
+"x" += 2
+"y" <?= "x" * 3
+"visited" ++
+set "flag"
+clear "other flag"
+"a" + 2 * ( "b" - 1 ) == -4
+or
+    "reputation: Republic" >= 10
+    and
+        has "license: Pilot's"
+        "credits" != 0
+"#;
+
+        let parsed = dbg!(parse_conditions::<VerboseError<&str>>(0, &data));
+        assert!(parsed.is_ok());
+        let (remaining, conditions) = parsed.unwrap();
+        assert_eq!(remaining, "");
+
+        assert_eq!(
+            conditions,
+            vec![
+                Condition::Comparison {
+                    left: ConditionExpr::Condition("combat rating"),
+                    operator: ComparisonOperator::Greater,
+                    right: ConditionExpr::Number(100),
+                },
+                Condition::Has("event: foo"),
+                Condition::Not("x"),
+                Condition::Never,
+                Condition::Assignment {
+                    name: "x",
+                    operator: AssignmentOperator::Add,
+                    value: ConditionExpr::Number(2),
+                },
+                Condition::Assignment {
+                    name: "y",
+                    operator: AssignmentOperator::Min,
+                    value: ConditionExpr::Operation(
+                        Box::new(ConditionExpr::Condition("x")),
+                        ArithmeticOperator::Multiply,
+                        Box::new(ConditionExpr::Number(3)),
+                    ),
+                },
+                Condition::Increment("visited"),
+                Condition::Set("flag"),
+                Condition::Clear("other flag"),
+                Condition::Comparison {
+                    left: ConditionExpr::Operation(
+                        Box::new(ConditionExpr::Condition("a")),
+                        ArithmeticOperator::Add,
+                        Box::new(ConditionExpr::Operation(
+                            Box::new(ConditionExpr::Number(2)),
+                            ArithmeticOperator::Multiply,
+                            Box::new(ConditionExpr::Operation(
+                                Box::new(ConditionExpr::Condition("b")),
+                                ArithmeticOperator::Subtract,
+                                Box::new(ConditionExpr::Number(1)),
+                            )),
+                        )),
+                    ),
+                    operator: ComparisonOperator::Equal,
+                    right: ConditionExpr::Number(-4),
+                },
+                Condition::Or(vec![
+                    Condition::Comparison {
+                        left: ConditionExpr::Condition("reputation: Republic"),
+                        operator: ComparisonOperator::GreaterOrEqual,
+                        right: ConditionExpr::Number(10),
+                    },
+                    Condition::And(vec![
+                        Condition::Has("license: Pilot's"),
+                        Condition::Comparison {
+                            left: ConditionExpr::Condition("credits"),
+                            operator: ComparisonOperator::NotEqual,
+                            right: ConditionExpr::Number(0),
+                        },
+                    ]),
+                ]),
+            ]
+        );
+    }
+}
@@ -2,12 +2,11 @@ use nom::{
     bytes::complete::tag,
     character::complete::{line_ending, space1},
     error::context,
-    number::complete::float,
     sequence::tuple,
     IResult,
 };
 
-use crate::helpers::{indent, string};
+use crate::helpers::{float, indent, string};
 use crate::types::Effect;
 use crate::DataError;
 
@@ -5,12 +5,11 @@ use nom::{
     combinator::opt,
     error::{context, ParseError},
     multi::count,
-    number::complete::float,
     sequence::tuple,
     IResult,
 };
 
-use crate::helpers::{indent, resource_path, string};
+use crate::helpers::{float, indent, resource_path, string};
 use crate::types::{Galaxy, Position};
 
 pub fn parse_galaxy<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Galaxy<'a>, E> {
@@ -0,0 +1,147 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, space1},
+    combinator::map,
+    error::{context, ParseError},
+    multi::{count, many0},
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::helpers::{float, indent, skip_block, string};
+use crate::types::{Color, Government};
+use crate::DataError;
+
+pub fn parse_government<'a>(
+    input: &'a str,
+) -> IResult<&'a str, Government<'a>, DataError<&'a str>> {
+    let (input, (_, _, name, _)) = context(
+        "government tag",
+        tuple((tag("government"), space1, string, line_ending)),
+    )(input)?;
+
+    let mut builder = crate::types::GovernmentBuilder::default();
+    builder.name(name);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, display_name, "\"display name\"", string, input, builder);
+        crate::parse_item_in_loop!(1, color, parse_color, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            player_reputation,
+            "\"player reputation\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            attitudes,
+            "\"attitude toward\"",
+            parse_values,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            penalties,
+            "\"penalty for\"",
+            parse_values,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, bribe, float, input, builder);
+        crate::parse_item_in_loop!(1, fine, float, input, builder);
+
+        let skipped: IResult<_, _, DataError<&'a str>> = skip_block(1)(input);
+        if let Ok((remaining, _)) = skipped {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|government| (input, government))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("government"),
+            })
+        })
+}
+
+fn parse_color<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Color<'a>, E> {
+    alt((
+        map(
+            tuple((float, space1, float, space1, float, line_ending)),
+            |(red, _, green, _, blue, _)| Color::Rgb(red, green, blue),
+        ),
+        map(tuple((string, line_ending)), |(name, _)| Color::Named(name)),
+    ))(input)
+}
+
+fn parse_values<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, f32)>, E> {
+    preceded(
+        line_ending,
+        many0(map(
+            tuple((count(indent, 2), string, space1, float, line_ending)),
+            |(_, name, _, value, _)| (name, value),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_parse_government() {
+        let data = r#"government "Republic"
+	"display name" "Republic"
+	swizzle 0
+	color .91 .35 .13
+	"player reputation" 10
+	"attitude toward"
+		"Free Worlds" -.1
+		"Pirate" -.5
+	"penalty for"
+		assist -0.1
+		destroy 1
+	bribe .02
+	fine 1.5
+	"hostile hail" "hostile republic"
+"#;
+
+        let parsed = dbg!(parse_government(data));
+        assert!(parsed.is_ok());
+        let (remaining, government) = parsed.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            government,
+            Government {
+                name: "Republic",
+                display_name: Some("Republic"),
+                color: Some(Color::Rgb(0.91, 0.35, 0.13)),
+                player_reputation: Some(10.0),
+                attitudes: vec![("Free Worlds", -0.1), ("Pirate", -0.5)],
+                penalties: vec![("assist", -0.1), ("destroy", 1.0)],
+                bribe: Some(0.02),
+                fine: Some(1.5),
+            }
+        );
+
+        let (_, government) = parse_government(
+            "government Pirate\n\tcolor \"governments: Pirate\"\n\t\"player reputation\" -1000\n",
+        )
+        .unwrap();
+        assert_eq!(government.color, Some(Color::Named("governments: Pirate")));
+        assert!(government.attitudes.is_empty());
+    }
+}
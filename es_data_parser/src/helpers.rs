@@ -1,12 +1,15 @@
-use std::ops::Neg;
+use std::convert::TryFrom;
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
     bytes::complete::take_until,
-    character::complete::{char, digit1, line_ending, space1, tab},
-    combinator::{cut, opt},
+    character::complete::{
+        char, digit0, digit1, line_ending, not_line_ending, one_of, space1, tab,
+    },
+    combinator::{cut, map, map_res, not, opt, recognize},
     error::{context, ErrorKind, ParseError},
+    multi::{count, many0},
     sequence::{preceded, terminated, tuple},
     AsChar, IResult, InputTakeAtPosition,
 };
@@ -65,24 +68,67 @@ pub fn resource_path<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a
     )(input)
 }
 
-pub fn integer<'a, T: std::str::FromStr, E: ParseError<&'a str>>(
+/// recognize a numeric token the way ES reads them: an optional sign, digits with an optional
+/// decimal part, and an optional exponent
+pub fn number_token<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, &'a str, E> {
+    context(
+        "number",
+        recognize(tuple((
+            opt(one_of("+-")),
+            alt((
+                recognize(tuple((digit1, opt(tuple((char('.'), digit0)))))),
+                recognize(tuple((char('.'), digit1))),
+            )),
+            opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+        ))),
+    )(input)
+}
+
+fn integer_value<T: TryFrom<i64>>(token: &str) -> Result<T, ()> {
+    let value = match token.parse::<i64>() {
+        Ok(value) => value,
+        Err(_) => {
+            let value = token.parse::<f64>().map_err(|_| ())?;
+            if !value.is_finite()
+                || value.fract() != 0.0
+                || value < i64::MIN as f64
+                || value >= i64::MAX as f64
+            {
+                return Err(());
+            }
+            value as i64
+        }
+    };
+    T::try_from(value).map_err(|_| ())
+}
+
+/// parse a numeric token as an integer, failing if it has a fractional part or doesn't fit
+pub fn integer<'a, T: TryFrom<i64>, E: ParseError<&'a str>>(
     input: &'a str,
-) -> IResult<&'a str, T, E>
-where
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
-{
-    context("integer", digit1)(input).map(|(input, value)| (input, value.parse::<T>().unwrap()))
+) -> IResult<&'a str, T, E> {
+    context("integer", map_res(number_token, integer_value))(input)
 }
 
-pub fn integer_u32<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u32, E> {
-    context("integer u32", digit1)(input)
-        .map(|(input, value)| (input, value.parse::<u32>().unwrap()))
+/// parse a numeric token as a float, failing if it doesn't fit
+pub fn float<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, f32, E> {
+    context(
+        "float",
+        map_res(number_token, |token: &str| {
+            token.parse::<f32>().map_err(|_| ()).and_then(|value| {
+                if value.is_finite() {
+                    Ok(value)
+                } else {
+                    Err(())
+                }
+            })
+        }),
+    )(input)
 }
-pub fn integer_i32<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i32, E> {
-    context("integer i32", tuple((opt(tag("-")), digit1)))(input).map(|(input, (minus, value))| {
-        let value = value.parse::<i32>().unwrap();
-        (input, if minus.is_some() { value.neg() } else { value })
-    })
+
+/// parse a numeric token as a float truncated to an integer, as ES does for the values it keeps as
+/// integers
+pub fn truncated_float<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i32, E> {
+    map(float, |value| value as i32)(input)
 }
 
 pub fn comment_hole<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
@@ -96,6 +142,32 @@ pub fn comment_hole<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a s
     .map(|(remaining, _)| (remaining, ()))
 }
 
+/// skip a line at the given indentation, with all the lines more indented that follow it
+pub fn skip_block<'a, E: ParseError<&'a str>>(
+    nb_indent: usize,
+) -> impl Fn(&'a str) -> IResult<&'a str, (), E> {
+    move |input: &'a str| {
+        context(
+            "block to ignore",
+            tuple((
+                count(indent, nb_indent),
+                not(indent),
+                not(line_ending),
+                not_line_ending,
+                line_ending,
+                many0(alt((
+                    map(
+                        tuple((count(indent, nb_indent + 1), not_line_ending, line_ending)),
+                        |_| (),
+                    ),
+                    map(line_ending, |_| ()),
+                ))),
+            )),
+        )(input)
+        .map(|(remaining, _)| (remaining, ()))
+    }
+}
+
 /// helper to build function that will parse a field with an indententation
 #[macro_export]
 macro_rules! parse_item_with_indent {
@@ -162,19 +234,29 @@ macro_rules! parse_item_in_loop {
 #[macro_export]
 macro_rules! parse_items_in_loop {
     ($nb_indent:expr, $field:ident, $subparser:expr, $input:ident, $builder:ident) => {
+        crate::parse_items_in_loop!(
+            $nb_indent,
+            $field,
+            stringify!($field),
+            $subparser,
+            $input,
+            $builder
+        )
+    };
+    ($nb_indent:expr, $field:ident, $tag:expr, $subparser:expr, $input:ident, $builder:ident) => {
         let peeked: IResult<_, _, (&str, nom::error::ErrorKind)> =
             nom::combinator::peek(nom::sequence::tuple((
                 nom::multi::count(indent, $nb_indent),
-                nom::bytes::complete::tag(stringify!($field)),
+                nom::bytes::complete::tag($tag),
             )))($input);
         if peeked.is_ok() {
             let (remaining, extracted) = nom::error::context(
-                stringify!($field),
+                $tag,
                 nom::multi::many1(nom::sequence::terminated(
                     nom::sequence::preceded(
                         nom::sequence::tuple((
                             nom::multi::count(indent, $nb_indent),
-                            nom::bytes::complete::tag(stringify!($field)),
+                            nom::bytes::complete::tag($tag),
                             nom::combinator::opt(space1),
                         )),
                         $subparser,
@@ -191,3 +273,39 @@ macro_rules! parse_items_in_loop {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_numbers() {
+        assert_eq!(float::<VerboseError<&str>>("12"), Ok(("", 12.0)));
+        assert_eq!(float::<VerboseError<&str>>("-0.5 "), Ok((" ", -0.5)));
+        assert_eq!(float::<VerboseError<&str>>("+.25"), Ok(("", 0.25)));
+        assert_eq!(float::<VerboseError<&str>>("3."), Ok(("", 3.0)));
+        assert_eq!(float::<VerboseError<&str>>("1.5e3"), Ok(("", 1500.0)));
+        assert_eq!(float::<VerboseError<&str>>("2E-2"), Ok(("", 0.02)));
+        assert!(float::<VerboseError<&str>>("1e50").is_err());
+        assert!(float::<VerboseError<&str>>("-").is_err());
+        assert!(float::<VerboseError<&str>>("abc").is_err());
+    }
+
+    #[test]
+    fn can_parse_integers() {
+        assert_eq!(integer::<u32, VerboseError<&str>>("100\n"), Ok(("\n", 100)));
+        assert_eq!(integer::<i32, VerboseError<&str>>("-20"), Ok(("", -20)));
+        assert_eq!(integer::<i32, VerboseError<&str>>("+7"), Ok(("", 7)));
+        assert_eq!(
+            integer::<u64, VerboseError<&str>>("2.5e6"),
+            Ok(("", 2_500_000))
+        );
+        assert!(integer::<u32, VerboseError<&str>>("-20").is_err());
+        assert!(integer::<u32, VerboseError<&str>>("0.5").is_err());
+        assert!(integer::<u16, VerboseError<&str>>("70000").is_err());
+        assert!(integer::<i64, VerboseError<&str>>("99999999999999999999").is_err());
+        assert!(integer::<i64, VerboseError<&str>>("9223372036854775808").is_err());
+    }
+}
@@ -23,10 +23,15 @@ mod errors;
 mod helpers;
 use errors::DataError;
 
+mod condition;
 mod effect;
 mod galaxy;
+mod government;
+mod mission;
 mod outfit;
+mod pilot;
 mod planet;
+mod sale;
 mod ship;
 mod start;
 mod system;
@@ -38,6 +43,20 @@ pub fn parse<'a>(input: &'a str) -> Vec<Object<'a>> {
         .unwrap_or_else(|_| vec![])
 }
 
+/// Parse a list of Endless Sky conditions, one per line, returning an empty list on error
+pub fn parse_conditions<'a>(input: &'a str) -> Vec<Condition<'a>> {
+    all_consuming(|input| condition::parse_conditions::<DataError<&'a str>>(0, input))(input)
+        .map(|(_, conditions)| conditions)
+        .unwrap_or_else(|_| vec![])
+}
+
+/// Parse an Endless Sky pilot save file, returning `None` on error
+pub fn parse_pilot<'a>(input: &'a str) -> Option<Pilot<'a>> {
+    all_consuming(pilot::parse_pilot)(input)
+        .map(|(_, pilot)| pilot)
+        .ok()
+}
+
 /// Parse Endless Sky data
 pub fn validate<'a>(input: &'a str) -> IResult<&'a str, Vec<Object<'a>>, DataError<&'a str>> {
     all_consuming(many1(alt((
@@ -60,6 +79,22 @@ pub fn validate<'a>(input: &'a str) -> IResult<&'a str, Vec<Object<'a>>, DataErr
         |input| {
             effect::parse_effect(input).map(|(input, parsed)| (input, Some(Object::Effect(parsed))))
         },
+        |input| {
+            sale::parse_shipyard(input)
+                .map(|(input, parsed)| (input, Some(Object::Shipyard(parsed))))
+        },
+        |input| {
+            sale::parse_outfitter(input)
+                .map(|(input, parsed)| (input, Some(Object::Outfitter(parsed))))
+        },
+        |input| {
+            government::parse_government(input)
+                .map(|(input, parsed)| (input, Some(Object::Government(parsed))))
+        },
+        |input| {
+            mission::parse_mission(input)
+                .map(|(input, parsed)| (input, Some(Object::Mission(parsed))))
+        },
         |input| line_ending(input).map(|(input, _)| (input, None)),
         |input| helpers::comment_hole(input).map(|(input, _)| (input, None)),
     ))))(input)
@@ -101,6 +136,12 @@ planet MyPlanet
 		threshold 3000
 		fleet "Impressive Fleet" 18
 
+shipyard "Some Ships"
+	"Shuttle"
+
+outfitter "Basic Outifts"
+	"Hyperdrive"
+
 start
 	system "my system"
 	planet "this planet"
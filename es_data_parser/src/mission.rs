@@ -0,0 +1,450 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, not_line_ending, space0, space1},
+    combinator::{map, opt},
+    error::{context, ParseError},
+    multi::{count, many0, separated_list},
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::condition::{parse_condition, parse_conditions};
+use crate::helpers::{indent, integer, skip_block, string};
+use crate::types::{
+    Condition, LocationFilter, Mission, MissionAction, MissionCargo, MissionDeadline,
+    MissionLocation, MissionPayment, MissionPlace, MissionTrigger,
+};
+use crate::DataError;
+
+pub fn parse_mission<'a>(input: &'a str) -> IResult<&'a str, Mission<'a>, DataError<&'a str>> {
+    let (input, (_, _, id, _)) = context(
+        "mission tag",
+        tuple((tag("mission"), space1, string, line_ending)),
+    )(input)?;
+
+    let mut builder = crate::types::MissionBuilder::default();
+    builder.id(id);
+    let mut actions = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, name, string, input, builder);
+        crate::parse_item_in_loop!(1, description, string, input, builder);
+        crate::parse_item_in_loop!(1, repeat, opt(integer::<u32, _>), input, builder);
+        crate::parse_item_in_loop!(1, deadline, parse_deadline, input, builder);
+        crate::parse_item_in_loop!(1, cargo, parse_cargo, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            passengers,
+            terminated(integer::<u32, _>, not_line_ending),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, source, parse_place, input, builder);
+        crate::parse_item_in_loop!(1, destination, parse_place, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            to_offer,
+            "to offer",
+            parse_block_conditions,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            to_complete,
+            "to complete",
+            parse_block_conditions,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            1,
+            to_fail,
+            "to fail",
+            parse_block_conditions,
+            input,
+            builder
+        );
+
+        let location: IResult<_, _, DataError<&'a str>> = terminated(
+            preceded(
+                indent,
+                alt((
+                    map(tag("job"), |_| MissionLocation::Job),
+                    map(tag("landing"), |_| MissionLocation::Landing),
+                )),
+            ),
+            tuple((space0, line_ending, many0(line_ending))),
+        )(input);
+        if let Ok((remaining, location)) = location {
+            builder.location(location);
+            input = remaining;
+            continue;
+        }
+
+        // actions can be anywhere in the block, and have their own fields
+        let action: IResult<_, _, DataError<&'a str>> =
+            preceded(tuple((indent, tag("on"), space1)), parse_action)(input);
+        if let Ok((remaining, action)) = action {
+            actions.push(action);
+            input = remaining;
+            continue;
+        }
+
+        let skipped: IResult<_, _, DataError<&'a str>> = skip_block(1)(input);
+        if let Ok((remaining, _)) = skipped {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+    builder.actions(actions);
+
+    builder
+        .build()
+        .map(|mission| (input, mission))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("mission"),
+            })
+        })
+}
+
+fn parse_deadline<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, MissionDeadline, E> {
+    map(
+        tuple((opt(integer), opt(preceded(space1, integer)))),
+        |(days, multiplier)| MissionDeadline { days, multiplier },
+    )(input)
+}
+
+fn parse_cargo<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, MissionCargo<'a>, E> {
+    // the random variations of the tonnage that may follow are ignored
+    let (input, (kind, _, tons, _)) = tuple((string, space1, integer, not_line_ending))(input)?;
+    Ok((input, MissionCargo { kind, tons }))
+}
+
+fn parse_block_conditions<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<Condition<'a>>, E> {
+    preceded(tuple((space0, line_ending)), |input| {
+        parse_conditions(2, input)
+    })(input)
+}
+
+/// parse a planet, either by it's name on the same line or with a filter in a block
+fn parse_place<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, MissionPlace<'a>, E> {
+    alt((
+        map(string, MissionPlace::Planet),
+        map(
+            preceded(tuple((space0, line_ending)), parse_filter),
+            MissionPlace::Filter,
+        ),
+    ))(input)
+}
+
+fn parse_filter<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, LocationFilter<'a>, E> {
+    let mut filter = LocationFilter::default();
+    let mut input = input;
+    loop {
+        let line: IResult<_, _, E> = terminated(
+            tuple((
+                count(indent, 2),
+                alt((
+                    tag("planet"),
+                    tag("system"),
+                    tag("government"),
+                    tag("attributes"),
+                )),
+                space1,
+                separated_list(space1, string),
+            )),
+            tuple((space0, line_ending, many0(line_ending))),
+        )(input);
+        if let Ok((remaining, (_, kind, _, names))) = line {
+            match kind {
+                "planet" => filter.planets.extend(names),
+                "system" => filter.systems.extend(names),
+                "government" => filter.governments.extend(names),
+                _ => filter.attributes.push(names),
+            }
+            input = remaining;
+            continue;
+        }
+
+        // other filters, like the distance, are ignored
+        if let Ok((remaining, _)) = skip_block::<E>(2)(input) {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+    Ok((input, filter))
+}
+
+fn parse_action<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, MissionAction<'a>, E> {
+    let (input, (trigger, _, _)) = tuple((
+        alt((
+            map(tag("offer"), |_| MissionTrigger::Offer),
+            map(tag("accept"), |_| MissionTrigger::Accept),
+            map(tag("decline"), |_| MissionTrigger::Decline),
+            map(tag("complete"), |_| MissionTrigger::Complete),
+            map(tag("fail"), |_| MissionTrigger::Fail),
+            map(tag("visit"), |_| MissionTrigger::Visit),
+        )),
+        space0,
+        line_ending,
+    ))(input)?;
+
+    let mut action = MissionAction {
+        trigger,
+        payment: None,
+        conditions: vec![],
+        outfits: vec![],
+        events: vec![],
+    };
+    let mut input = input;
+    loop {
+        let line_end = || tuple((space0, line_ending, many0(line_ending)));
+
+        let payment: IResult<_, _, E> = terminated(
+            preceded(
+                tuple((count(indent, 2), tag("payment"))),
+                tuple((
+                    opt(preceded(space1, integer)),
+                    opt(preceded(space1, integer)),
+                )),
+            ),
+            line_end(),
+        )(input);
+        if let Ok((remaining, (base, multiplier))) = payment {
+            action.payment = Some(MissionPayment { base, multiplier });
+            input = remaining;
+            continue;
+        }
+
+        let outfit: IResult<_, _, E> = terminated(
+            preceded(
+                tuple((count(indent, 2), tag("outfit"), space1)),
+                tuple((string, opt(preceded(space1, integer)))),
+            ),
+            line_end(),
+        )(input);
+        if let Ok((remaining, (name, count))) = outfit {
+            action.outfits.push((name, count.unwrap_or(1)));
+            input = remaining;
+            continue;
+        }
+
+        let event: IResult<_, _, E> = terminated(
+            preceded(
+                tuple((count(indent, 2), tag("event"), space1)),
+                tuple((string, opt(preceded(space1, integer)))),
+            ),
+            line_end(),
+        )(input);
+        if let Ok((remaining, event)) = event {
+            action.events.push(event);
+            input = remaining;
+            continue;
+        }
+
+        let condition: IResult<_, _, E> =
+            terminated(|input| parse_condition(2, input), many0(line_ending))(input);
+        if let Ok((remaining, condition)) = condition {
+            action.conditions.push(condition);
+            input = remaining;
+            continue;
+        }
+
+        // dialogs and conversations are ignored
+        if let Ok((remaining, _)) = skip_block::<E>(2)(input) {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+    Ok((input, action))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::{ComparisonOperator, ConditionExpr};
+
+    #[test]
+    fn can_parse_mission() {
+        let data = r#"mission "Deliver Food"
+	name "Food to <planet>"
+	description "Bring <cargo> to <destination> by <date>."
+	job
+	repeat
+	deadline 10 2
+	cargo "Food" 20 2 .1
+	passengers 3
+	source
+		government "Republic"
+		attributes "farming" "urban"
+		near "Sol" 1 5
+	destination "Earth"
+	to offer
+		"combat rating" > 10
+		random < 50
+	on accept
+		"food missions" ++
+	on visit
+		dialog `Your cargo is not all here.`
+	on complete
+		payment 1000 50
+		outfit "Jump Drive"
+		outfit "Stolen Goods" -2
+		event "food delivered" 5
+		set "fed the planet"
+		dialog
+			`Thanks!`
+
+	on enter "Sol"
+		dialog "ignored"
+	minor
+"#;
+
+        let parsed = dbg!(parse_mission(&data));
+        assert!(parsed.is_ok());
+        let (remaining, mission) = parsed.unwrap();
+        assert_eq!(remaining, "");
+
+        assert_eq!(mission.id, "Deliver Food");
+        assert_eq!(mission.name, Some("Food to <planet>"));
+        assert_eq!(
+            mission.description,
+            Some("Bring <cargo> to <destination> by <date>.")
+        );
+        assert_eq!(mission.location, MissionLocation::Job);
+        assert_eq!(mission.repeat, Some(None));
+        assert_eq!(
+            mission.deadline,
+            Some(MissionDeadline {
+                days: Some(10),
+                multiplier: Some(2),
+            })
+        );
+        assert_eq!(
+            mission.cargo,
+            Some(MissionCargo {
+                kind: "Food",
+                tons: 20,
+            })
+        );
+        assert_eq!(mission.passengers, Some(3));
+        assert_eq!(
+            mission.source,
+            Some(MissionPlace::Filter(LocationFilter {
+                planets: vec![],
+                systems: vec![],
+                governments: vec!["Republic"],
+                attributes: vec![vec!["farming", "urban"]],
+            }))
+        );
+        assert_eq!(mission.destination, Some(MissionPlace::Planet("Earth")));
+        assert_eq!(
+            mission.to_offer,
+            vec![
+                Condition::Comparison {
+                    left: ConditionExpr::Condition("combat rating"),
+                    operator: ComparisonOperator::Greater,
+                    right: ConditionExpr::Number(10),
+                },
+                Condition::Comparison {
+                    left: ConditionExpr::Condition("random"),
+                    operator: ComparisonOperator::Less,
+                    right: ConditionExpr::Number(50),
+                },
+            ]
+        );
+        assert!(mission.to_complete.is_empty());
+
+        assert_eq!(
+            mission
+                .actions
+                .iter()
+                .map(|action| action.trigger)
+                .collect::<Vec<_>>(),
+            vec![
+                MissionTrigger::Accept,
+                MissionTrigger::Visit,
+                MissionTrigger::Complete
+            ]
+        );
+        assert_eq!(
+            mission.actions[0].conditions,
+            vec![Condition::Increment("food missions")]
+        );
+        assert_eq!(
+            mission.actions[2],
+            MissionAction {
+                trigger: MissionTrigger::Complete,
+                payment: Some(MissionPayment {
+                    base: Some(1000),
+                    multiplier: Some(50),
+                }),
+                conditions: vec![Condition::Set("fed the planet")],
+                outfits: vec![("Jump Drive", 1), ("Stolen Goods", -2)],
+                events: vec![("food delivered", Some(5))],
+            }
+        );
+    }
+
+    #[test]
+    fn can_parse_mission_with_defaults() {
+        let data = r#"mission "Intro"
+	landing
+	deadline
+	destination
+		planet "Earth" "Mars"
+	on complete
+		payment
+"#;
+
+        let (_, mission) = parse_mission(&data).unwrap();
+        assert_eq!(mission.name, None);
+        assert_eq!(mission.location, MissionLocation::Landing);
+        assert_eq!(mission.repeat, None);
+        assert_eq!(
+            mission.deadline,
+            Some(MissionDeadline {
+                days: None,
+                multiplier: None,
+            })
+        );
+        assert_eq!(mission.source, None);
+        assert_eq!(
+            mission.destination,
+            Some(MissionPlace::Filter(LocationFilter {
+                planets: vec!["Earth", "Mars"],
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            mission.actions[0].payment,
+            Some(MissionPayment {
+                base: None,
+                multiplier: None,
+            })
+        );
+    }
+}
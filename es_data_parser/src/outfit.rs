@@ -1,16 +1,16 @@
 use nom::{
+    branch::alt,
     bytes::complete::tag,
     character::complete::{line_ending, space1},
     combinator::opt,
     error::context,
-    multi::separated_list,
-    number::complete::float,
-    sequence::{preceded, tuple},
+    multi::{count, many1, separated_list},
+    sequence::{preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-use crate::helpers::{indent, integer, integer_i32, integer_u32, resource_path, string};
-use crate::types::{Outfit, Weapon};
+use crate::helpers::{float, indent, integer, resource_path, string};
+use crate::types::{Outfit, Position, Submunition, Weapon};
 use crate::DataError;
 
 #[allow(clippy::cognitive_complexity)]
@@ -26,6 +26,16 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
     loop {
         crate::parse_item_in_loop!(1, plural, string, input, builder);
         crate::parse_item_in_loop!(1, category, string, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            licenses,
+            preceded(
+                line_ending,
+                many1(preceded(count(indent, 2), terminated(string, line_ending)))
+            ),
+            input,
+            builder
+        );
         crate::parse_item_in_loop!(1, cost, integer, input, builder);
         crate::parse_item_in_loop!(1, cost, "\"cost\"", integer, input, builder);
         crate::parse_item_in_loop!(1, unplunderable, integer, input, builder);
@@ -185,6 +195,14 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
         );
         crate::parse_item_in_loop!(1, illegal, "\"illegal\"", float, input, builder);
         crate::parse_item_in_loop!(1, map, "\"map\"", float, input, builder);
+        crate::parse_item_in_loop!(
+            1,
+            maintenance_costs,
+            "\"maintenance costs\"",
+            float,
+            input,
+            builder
+        );
         crate::parse_item_in_loop!(
             1,
             weapon_capacity,
@@ -297,7 +315,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             energy_capacity,
             "\"energy capacity\"",
-            integer_u32,
+            float,
             input,
             builder
         );
@@ -317,6 +335,15 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             input,
             builder
         );
+        crate::parse_item_in_loop!(
+            1,
+            hull_repair_rate,
+            "\"hull repair rate\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(1, hull_energy, "\"hull energy\"", float, input, builder);
         crate::parse_item_in_loop!(
             1,
             flare_sprite,
@@ -331,7 +358,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             turret_mounts,
             "\"turret mounts\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -341,7 +368,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             gatling_round_capacity,
             "\"gatling round capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -349,7 +376,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             javelin_capacity,
             "\"javelin capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -357,7 +384,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             meteor_capacity,
             "\"meteor capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -365,7 +392,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             rocket_capacity,
             "\"rocket capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -373,7 +400,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             sidewinder_capacity,
             "\"sidewinder capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -381,7 +408,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             torpedo_capacity,
             "\"torpedo capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -389,7 +416,7 @@ pub fn parse_outfit<'a>(input: &'a str) -> IResult<&'a str, Outfit<'a>, DataErro
             1,
             typhoon_capacity,
             "\"typhoon capacity\"",
-            integer_i32,
+            integer::<i32, _>,
             input,
             builder
         );
@@ -442,13 +469,19 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             builder
         );
         crate::parse_item_in_loop!(2, sound, string, input, builder);
-        crate::parse_item_in_loop!(2, ammo, string, input, builder);
+        crate::parse_item_in_loop!(
+            2,
+            ammo,
+            tuple((string, opt(preceded(space1, integer)))),
+            input,
+            builder
+        );
         crate::parse_item_in_loop!(2, icon, resource_path, input, builder);
         crate::parse_item_in_loop!(
             2,
             hit_effect,
             "\"hit effect\"",
-            tuple((string, opt(preceded(space1, integer_i32)))),
+            tuple((string, opt(preceded(space1, integer::<i32, _>)))),
             input,
             builder
         );
@@ -456,7 +489,7 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             2,
             fire_effect,
             "\"fire effect\"",
-            tuple((string, opt(preceded(space1, integer_i32)))),
+            tuple((string, opt(preceded(space1, integer::<i32, _>)))),
             input,
             builder
         );
@@ -464,15 +497,15 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             2,
             die_effect,
             "\"die effect\"",
-            tuple((string, opt(preceded(space1, integer_i32)))),
+            tuple((string, opt(preceded(space1, integer::<i32, _>)))),
             input,
             builder
         );
-        crate::parse_item_in_loop!(
+        crate::parse_items_in_loop!(
             2,
             submunition,
             "\"submunition\"",
-            tuple((string, opt(preceded(space1, integer_i32)))),
+            parse_submunition,
             input,
             builder
         );
@@ -497,6 +530,14 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             input,
             builder
         );
+        crate::parse_item_in_loop!(
+            2,
+            range_override,
+            "\"range override\"",
+            float,
+            input,
+            builder
+        );
         crate::parse_item_in_loop!(2, reload, "\"reload\"", float, input, builder);
         crate::parse_item_in_loop!(2, firing_energy, "\"firing energy\"", float, input, builder);
         crate::parse_item_in_loop!(2, firing_force, "\"firing force\"", float, input, builder);
@@ -506,6 +547,26 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
         crate::parse_item_in_loop!(2, shield_damage, "\"shield damage\"", float, input, builder);
         crate::parse_item_in_loop!(2, hull_damage, "\"hull damage\"", float, input, builder);
         crate::parse_item_in_loop!(2, heat_damage, "\"heat damage\"", float, input, builder);
+        crate::parse_item_in_loop!(2, fuel_damage, "\"fuel damage\"", float, input, builder);
+        crate::parse_item_in_loop!(2, energy_damage, "\"energy damage\"", float, input, builder);
+        crate::parse_item_in_loop!(2, ion_damage, "\"ion damage\"", float, input, builder);
+        crate::parse_item_in_loop!(
+            2,
+            disruption_damage,
+            "\"disruption damage\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            2,
+            slowing_damage,
+            "\"slowing damage\"",
+            float,
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(2, piercing, "\"piercing\"", float, input, builder);
         crate::parse_item_in_loop!(2, acceleration, "\"acceleration\"", float, input, builder);
         crate::parse_item_in_loop!(2, drag, "\"drag\"", float, input, builder);
         crate::parse_item_in_loop!(2, turn, "\"turn\"", float, input, builder);
@@ -560,11 +621,35 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             input,
             builder
         );
+        crate::parse_item_in_loop!(
+            2,
+            safe,
+            "\"safe\"",
+            |input| Ok((input, true)),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            2,
+            phasing,
+            "\"phasing\"",
+            |input| Ok((input, true)),
+            input,
+            builder
+        );
+        crate::parse_item_in_loop!(
+            2,
+            no_damage_scaling,
+            "\"no damage scaling\"",
+            |input| Ok((input, true)),
+            input,
+            builder
+        );
         crate::parse_item_in_loop!(
             2,
             burst_count,
             "\"burst count\"",
-            integer_u32,
+            integer::<u32, _>,
             input,
             builder
         );
@@ -572,7 +657,7 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
             2,
             burst_reload,
             "\"burst reload\"",
-            integer_u32,
+            integer::<u32, _>,
             input,
             builder
         );
@@ -592,6 +677,63 @@ pub fn parse_weapon<'a>(input: &'a str) -> IResult<&'a str, Weapon<'a>, DataErro
         })
 }
 
+fn parse_submunition<'a>(input: &'a str) -> IResult<&'a str, Submunition<'a>, DataError<&'a str>> {
+    let (input, (name, nb)) = context(
+        "submunition",
+        tuple((string, opt(preceded(space1, integer::<u32, _>)))),
+    )(input)?;
+
+    let mut builder = crate::types::SubmunitionBuilder::default();
+    builder.name(name);
+    if let Some(nb) = nb {
+        builder.count(nb);
+    }
+    let mut input = input;
+    loop {
+        let facing: IResult<_, _, DataError<&str>> = preceded(
+            tuple((
+                line_ending,
+                count(indent, 3),
+                alt((tag("facing"), tag("\"facing\""))),
+                space1,
+            )),
+            float,
+        )(input);
+        if let Ok((remaining, facing)) = facing {
+            builder.facing(facing);
+            input = remaining;
+            continue;
+        }
+        let offset: IResult<_, _, DataError<&str>> = preceded(
+            tuple((
+                line_ending,
+                count(indent, 3),
+                alt((tag("offset"), tag("\"offset\""))),
+                space1,
+            )),
+            separated_pair(float, space1, float),
+        )(input);
+        if let Ok((remaining, (x, y))) = offset {
+            builder.offset(Position { x, y });
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|submunition| (input, submunition))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("submunition"),
+            })
+        })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -600,6 +742,9 @@ mod test {
     fn can_parse_outfit() {
         let data = r#"outfit "My Little Engine"
     category "Engines"
+    licenses
+        Navy
+        "City-Ship"
     "cost" 20000
     thumbnail "outfit/little engines"
     "mass" 20
@@ -624,5 +769,83 @@ mod test {
 
         let engine = result.1;
         assert_eq!(engine.name, "My Little Engine");
+        assert_eq!(engine.licenses, vec!["Navy", "City-Ship"]);
+    }
+
+    #[test]
+    fn can_parse_weapon() {
+        let data = r#"outfit "My Little Gun"
+    category "Guns"
+    "cost" 5000
+    "mass" 5
+    "outfit space" -5
+    "weapon capacity" -5
+    "gun ports" -1
+    weapon
+        sprite "projectile/little"
+            "frame rate" 10
+        sound "little"
+        ammo "Little Ammo" 2
+        "hit effect" "little hit" 3
+        "submunition" "Little Fragment" 4
+            facing 45
+            offset 0 -2.5
+        "submunition" "Other Fragment"
+        "velocity" 12
+        "lifetime" 40
+        "range override" 600
+        "reload" 8
+        "firing energy" 1.5
+        "shield damage" 10
+        "hull damage" 5
+        "ion damage" 0.5
+        "disruption damage" 2
+        "slowing damage" .3
+        "piercing" 0.2
+        "safe"
+        "phasing"
+        "no damage scaling"
+"#;
+
+        let parsed = dbg!(parse_outfit(&data));
+        assert!(parsed.is_ok());
+        let result = parsed.unwrap();
+        assert_eq!(result.0, "");
+
+        let weapon = result.1.weapon.unwrap();
+        assert_eq!(weapon.ammo, Some(("Little Ammo", Some(2))));
+        assert_eq!(weapon.hit_effect, Some(("little hit", Some(3))));
+        assert_eq!(
+            weapon.submunition,
+            vec![
+                Submunition {
+                    name: "Little Fragment",
+                    count: 4,
+                    facing: Some(45.0),
+                    offset: Some(Position { x: 0.0, y: -2.5 }),
+                },
+                Submunition {
+                    name: "Other Fragment",
+                    count: 1,
+                    facing: None,
+                    offset: None,
+                }
+            ]
+        );
+        assert_eq!(weapon.velocity, Some(12.0));
+        assert_eq!(weapon.lifetime, Some(40.0));
+        assert_eq!(weapon.range_override, Some(600.0));
+        assert_eq!(weapon.reload, Some(8.0));
+        assert_eq!(weapon.firing_energy, Some(1.5));
+        assert_eq!(weapon.shield_damage, Some(10.0));
+        assert_eq!(weapon.hull_damage, Some(5.0));
+        assert_eq!(weapon.ion_damage, Some(0.5));
+        assert_eq!(weapon.disruption_damage, Some(2.0));
+        assert_eq!(weapon.slowing_damage, Some(0.3));
+        assert_eq!(weapon.piercing, Some(0.2));
+        assert!(weapon.safe);
+        assert!(weapon.phasing);
+        assert!(weapon.no_damage_scaling);
+        assert!(!weapon.stream);
     }
 }
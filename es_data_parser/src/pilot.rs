@@ -0,0 +1,309 @@
+use nom::{
+    branch::{alt, permutation},
+    bytes::complete::tag,
+    character::complete::{line_ending, space1},
+    combinator::{map, opt},
+    error::{context, ParseError},
+    multi::{count, many0},
+    sequence::{preceded, tuple},
+    IResult,
+};
+
+use crate::helpers::{comment_hole, date, float, indent, integer, skip_block, string};
+use crate::types::{Mortgage, Pilot, PilotAccount, PilotShip};
+use crate::DataError;
+
+pub fn parse_pilot<'a>(input: &'a str) -> IResult<&'a str, Pilot<'a>, DataError<&'a str>> {
+    let (input, (_, _, first_name, _, last_name, _)) = context(
+        "pilot tag",
+        tuple((tag("pilot"), space1, string, space1, string, line_ending)),
+    )(input)?;
+
+    let mut builder = crate::types::PilotBuilder::default();
+    builder.first_name(first_name);
+    builder.last_name(last_name);
+    let mut input = input;
+    loop {
+        if input.is_empty() {
+            break;
+        }
+        crate::parse_item_in_loop!(0, date, date, input, builder);
+        crate::parse_item_in_loop!(0, system, string, input, builder);
+        crate::parse_item_in_loop!(0, planet, string, input, builder);
+        crate::parse_item_in_loop!(0, account, parse_account, input, builder);
+        crate::parse_items_in_loop!(0, ships, "ship", parse_ship, input, builder);
+        crate::parse_item_in_loop!(0, conditions, parse_conditions, input, builder);
+        crate::parse_item_in_loop!(
+            0,
+            reputations,
+            "\"reputation with\"",
+            parse_reputations,
+            input,
+            builder
+        );
+        crate::parse_items_in_loop!(0, visited_systems, "visited", string, input, builder);
+        crate::parse_items_in_loop!(
+            0,
+            visited_planets,
+            "\"visited planet\"",
+            string,
+            input,
+            builder
+        );
+
+        let skipped: IResult<_, _, DataError<&'a str>> =
+            alt((skip_block(0), comment_hole, map(line_ending, |_| ())))(input);
+        if let Ok((remaining, _)) = skipped {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|pilot| (input, pilot))
+        .map_err(|error| {
+            nom::Err::Failure(DataError::DataBuilderError {
+                input,
+                error,
+                data_type: String::from("pilot"),
+            })
+        })
+}
+
+fn parse_account<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, PilotAccount<'a>, E> {
+    let (input, _) = line_ending(input)?;
+
+    let mut builder = crate::types::PilotAccountBuilder::default();
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, credits, integer::<u64, _>, input, builder);
+        crate::parse_item_in_loop!(1, score, integer::<u32, _>, input, builder);
+        crate::parse_items_in_loop!(1, mortgages, "mortgage", parse_mortgage, input, builder);
+
+        if let Ok((remaining, _)) = skip_block::<E>(1)(input) {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|account| (input, account))
+        .map_err(|_| nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Verify)))
+}
+
+fn parse_mortgage<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, (&'a str, Mortgage), E> {
+    let (input, (kind, _)) = tuple((string, line_ending))(input)?;
+    let (input, (principal, interest, term)) =
+        permutation((parse_principal, parse_interest, parse_term))(input)?;
+
+    Ok((
+        input,
+        (
+            kind,
+            Mortgage {
+                principal,
+                interest,
+                term,
+            },
+        ),
+    ))
+}
+
+crate::parse_item_with_indent!(2, parse_principal, principal, integer, u64);
+crate::parse_item_with_indent!(2, parse_interest, interest, float, f32);
+crate::parse_item_with_indent!(2, parse_term, term, integer, u16);
+
+fn parse_ship<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, PilotShip<'a>, E> {
+    let (input, (model, _)) = tuple((string, line_ending))(input)?;
+
+    let mut builder = crate::types::PilotShipBuilder::default();
+    builder.model(model);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, name, string, input, builder);
+        crate::parse_item_in_loop!(1, outfits, parse_outfits, input, builder);
+        crate::parse_item_in_loop!(1, fuel, float, input, builder);
+        crate::parse_item_in_loop!(1, shields, float, input, builder);
+        crate::parse_item_in_loop!(1, hull, float, input, builder);
+        crate::parse_item_in_loop!(1, system, string, input, builder);
+        crate::parse_item_in_loop!(1, planet, string, input, builder);
+
+        if let Ok((remaining, _)) = skip_block::<E>(1)(input) {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|ship| (input, ship))
+        .map_err(|_| nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Verify)))
+}
+
+fn parse_outfits<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, u32)>, E> {
+    preceded(
+        line_ending,
+        many0(map(
+            tuple((
+                count(indent, 2),
+                string,
+                opt(preceded(space1, integer::<u32, _>)),
+                line_ending,
+            )),
+            |(_, name, count, _)| (name, count.unwrap_or(1)),
+        )),
+    )(input)
+}
+
+fn parse_conditions<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, i64)>, E> {
+    preceded(
+        line_ending,
+        many0(map(
+            tuple((
+                indent,
+                string,
+                opt(preceded(space1, integer::<i64, _>)),
+                line_ending,
+            )),
+            |(_, name, value, _)| (name, value.unwrap_or(1)),
+        )),
+    )(input)
+}
+
+fn parse_reputations<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, f32)>, E> {
+    preceded(
+        line_ending,
+        many0(map(
+            tuple((indent, string, space1, float, line_ending)),
+            |(_, name, _, value, _)| (name, value),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::types::Date;
+
+    #[test]
+    fn can_parse_pilot() {
+        let data = r#"pilot Bob Smith
+date 16 11 3013
+system Rutilicus
+planet "New Boston"
+clearance
+playtime 1234.5
+"reputation with"
+	Merchant 10
+	Republic 1
+account
+	credits 131000
+	score 400
+	mortgage Mortgage
+		principal 480000
+		interest 0.004
+		term 365
+	history
+		-1000
+ship Shuttle
+	name "Buzzard"
+	uuid 0123-4567
+	attributes
+		category Transport
+		"hull" 600
+	outfits
+		"Hyperdrive"
+		"nGVF-AA Fuel Cell" 2
+	crew 1
+	fuel 400
+	shields 450
+	hull 600
+	system Rutilicus
+	planet "New Boston"
+ship "Star Barge"
+	name "Swallow"
+	system Sol
+conditions
+	"license: Pilot's"
+	"ships: Transport" 2
+visited Rutilicus
+visited Sol
+"visited planet" "New Boston"
+"#;
+
+        let parsed = dbg!(parse_pilot(data));
+        assert!(parsed.is_ok());
+        let (remaining, pilot) = parsed.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!((pilot.first_name, pilot.last_name), ("Bob", "Smith"));
+        assert_eq!(
+            pilot.date,
+            Date {
+                day: 16,
+                month: 11,
+                year: 3013
+            }
+        );
+        assert_eq!(pilot.system, "Rutilicus");
+        assert_eq!(pilot.planet, Some("New Boston"));
+        assert_eq!(
+            pilot.account,
+            PilotAccount {
+                credits: 131000,
+                score: 400,
+                mortgages: vec![(
+                    "Mortgage",
+                    Mortgage {
+                        principal: 480000,
+                        interest: 0.004,
+                        term: 365
+                    }
+                )]
+            }
+        );
+        assert_eq!(pilot.ships.len(), 2);
+        assert_eq!(
+            pilot.ships[0],
+            PilotShip {
+                model: "Shuttle",
+                name: Some("Buzzard"),
+                outfits: vec![("Hyperdrive", 1), ("nGVF-AA Fuel Cell", 2)],
+                fuel: Some(400.0),
+                shields: Some(450.0),
+                hull: Some(600.0),
+                system: Some("Rutilicus"),
+                planet: Some("New Boston"),
+            }
+        );
+        assert_eq!(pilot.ships[1].model, "Star Barge");
+        assert_eq!(
+            pilot.conditions,
+            vec![("license: Pilot's", 1), ("ships: Transport", 2)]
+        );
+        assert_eq!(pilot.visited_systems, vec!["Rutilicus", "Sol"]);
+        assert_eq!(pilot.visited_planets, vec!["New Boston"]);
+        assert_eq!(
+            pilot.reputations,
+            vec![("Merchant", 10.0), ("Republic", 1.0)]
+        );
+    }
+}
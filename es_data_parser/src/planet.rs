@@ -4,12 +4,11 @@ use nom::{
     character::complete::{line_ending, space1},
     error::{context, ParseError},
     multi::{count, separated_list},
-    number::complete::float,
     sequence::tuple,
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
+use crate::helpers::{float, indent, integer, resource_path, string, truncated_float};
 use crate::types::{Fleet, Planet, Tribute};
 use crate::DataError;
 
@@ -65,7 +64,7 @@ pub fn parse_planet<'a>(input: &'a str) -> IResult<&'a str, Planet<'a>, DataErro
 }
 
 fn parse_tribute<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Tribute<'a>, E> {
-    let (input, (value, _)) = tuple((integer, line_ending))(input)?;
+    let (input, (value, _)) = tuple((truncated_float, line_ending))(input)?;
 
     let (input, (threshold, fleet)) = permutation((parse_threshold, parse_fleet))(input)?;
 
@@ -94,7 +93,7 @@ fn parse_fleet<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, F
     Ok((input, Fleet { kind, count }))
 }
 
-crate::parse_item_with_indent!(2, parse_threshold, threshold, integer, u32);
+crate::parse_item_with_indent!(2, parse_threshold, threshold, integer, i32);
 
 #[cfg(test)]
 mod test {
@@ -114,7 +113,7 @@ mod test {
 	outfitter "Advanced Outfits"
 	bribe 0.01
 	security 0.5
-	tribute 1000
+	tribute 1000.7
 		threshold 3000
 		fleet "Impressive Fleet" 18
 "#;
@@ -0,0 +1,75 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, space1},
+    combinator::map,
+    error::{context, ParseError},
+    multi::many0,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::helpers::{indent, string};
+use crate::types::Sale;
+
+/// Parse a list of ships sold together, that a planet can reference in it's shipyard
+pub fn parse_shipyard<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Sale<'a>, E> {
+    context("shipyard", |input| parse_sale("shipyard", input))(input)
+}
+
+/// Parse a list of outfits sold together, that a planet can reference in it's outfitter
+pub fn parse_outfitter<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Sale<'a>, E> {
+    context("outfitter", |input| parse_sale("outfitter", input))(input)
+}
+
+fn parse_sale<'a, E: ParseError<&'a str>>(
+    kind: &'static str,
+    input: &'a str,
+) -> IResult<&'a str, Sale<'a>, E> {
+    let (input, (_, _, name, _)) = tuple((tag(kind), space1, string, line_ending))(input)?;
+    let (input, items) = many0(alt((
+        map(terminated(preceded(indent, string), line_ending), Some),
+        map(line_ending, |_| None),
+    )))(input)?;
+
+    Ok((
+        input,
+        Sale {
+            name,
+            items: items.into_iter().flatten().collect(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use nom::error::VerboseError;
+
+    #[test]
+    fn can_parse_shipyard_and_outfitter() {
+        let data = r#"shipyard "Basic Ships"
+	"Shuttle"
+	"Star Barge"
+
+"#;
+        let parsed = parse_shipyard::<VerboseError<&str>>(data);
+        assert!(parsed.is_ok());
+        let shipyard = parsed.unwrap().1;
+        assert_eq!(shipyard.name, "Basic Ships");
+        assert_eq!(shipyard.items, vec!["Shuttle", "Star Barge"]);
+
+        let data = r#"outfitter "Common Outfits"
+	"Hyperdrive"
+	`"Bullet" Launcher`
+"#;
+        let parsed = parse_outfitter::<VerboseError<&str>>(data);
+        assert!(parsed.is_ok());
+        let outfitter = parsed.unwrap().1;
+        assert_eq!(outfitter.name, "Common Outfits");
+        assert_eq!(outfitter.items, vec!["Hyperdrive", "\"Bullet\" Launcher"]);
+    }
+}
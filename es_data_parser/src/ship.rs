@@ -1,15 +1,15 @@
 use nom::{
+    branch::alt,
     bytes::complete::tag,
     character::complete::{line_ending, space1},
-    combinator::{opt, peek},
+    combinator::{map, opt, peek},
     error::context,
     multi::{count, many0, many1},
-    number::complete::float,
     sequence::{preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
+use crate::helpers::{float, indent, integer, resource_path, string};
 use crate::types::{Ship, ShipAttributes, ShipWeapon, Sprite};
 use crate::DataError;
 
@@ -127,6 +127,14 @@ pub fn parse_ship<'a>(input: &'a str) -> IResult<&'a str, Ship<'a>, DataError<&'
     })
 }
 
+enum SpriteAttribute {
+    FrameTime(u32),
+    Delay(u32),
+    RandomStartFrame,
+    NoRepeat,
+    FrameRate(f32),
+}
+
 pub fn parse_sprite<'a>(
     indent_level: usize,
     input: &'a str,
@@ -137,60 +145,56 @@ pub fn parse_sprite<'a>(
         peek(tuple((line_ending, count(indent, indent_level + 2))))(input);
 
     if peeked.is_ok() {
-        let (input, frame_time) = opt(preceded(
-            tuple((
-                line_ending,
-                count(indent, indent_level + 2),
-                tag("\"frame time\""),
-                space1,
-            )),
-            integer,
-        ))(input)?;
-        let (input, delay) = opt(preceded(
-            tuple((
-                line_ending,
-                count(indent, indent_level + 2),
-                tag("\"delay\""),
-                space1,
+        let (input, attributes) = context(
+            "sprite attributes",
+            many0(preceded(
+                tuple((line_ending, count(indent, indent_level + 2))),
+                alt((
+                    map(
+                        preceded(tuple((tag("\"frame time\""), space1)), integer),
+                        SpriteAttribute::FrameTime,
+                    ),
+                    map(
+                        preceded(tuple((tag("\"delay\""), space1)), integer),
+                        SpriteAttribute::Delay,
+                    ),
+                    map(tag("\"random start frame\""), |_| {
+                        SpriteAttribute::RandomStartFrame
+                    }),
+                    map(tag("\"no repeat\""), |_| SpriteAttribute::NoRepeat),
+                    map(
+                        preceded(tuple((tag("\"frame rate\""), space1)), float),
+                        SpriteAttribute::FrameRate,
+                    ),
+                )),
             )),
-            integer,
-        ))(input)?;
-        let (input, random_start_frame) = opt(preceded(
-            tuple((line_ending, count(indent, indent_level + 2))),
-            tag("\"random start frame\""),
-        ))(input)?;
-        let (input, no_repeat) = opt(preceded(
-            tuple((line_ending, count(indent, indent_level + 2))),
-            tag("\"no repeat\""),
-        ))(input)?;
-        let (input, frame_rate) = opt(preceded(
-            tuple((
-                line_ending,
-                count(indent, indent_level + 2),
-                tag("\"frame rate\""),
-                space1,
-            )),
-            float,
-        ))(input)?;
-        let (input, random_start_frame_2) = opt(preceded(
-            tuple((line_ending, count(indent, indent_level + 2))),
-            tag("\"random start frame\""),
-        ))(input)?;
-        let (input, no_repeat_2) = opt(preceded(
-            tuple((line_ending, count(indent, indent_level + 2))),
-            tag("\"no repeat\""),
-        ))(input)?;
+        )(input)?;
 
         let (input, _) = line_ending(input)?;
 
+        let mut frame_time = None;
+        let mut delay = None;
+        let mut random_start_frame = false;
+        let mut no_repeat = false;
+        let mut frame_rate = None;
+        for attribute in attributes {
+            match attribute {
+                SpriteAttribute::FrameTime(value) => frame_time = Some(value),
+                SpriteAttribute::Delay(value) => delay = Some(value),
+                SpriteAttribute::RandomStartFrame => random_start_frame = true,
+                SpriteAttribute::NoRepeat => no_repeat = true,
+                SpriteAttribute::FrameRate(value) => frame_rate = Some(value),
+            }
+        }
+
         Ok((
             input,
             Sprite::Sprite {
                 name,
                 frame_time,
                 delay,
-                random_start_frame: random_start_frame.is_some() || random_start_frame_2.is_some(),
-                no_repeat: no_repeat.is_some() || no_repeat_2.is_some(),
+                random_start_frame,
+                no_repeat,
                 frame_rate,
             },
         ))
@@ -220,8 +224,8 @@ pub fn parse_ship_attributes<'a>(
         crate::parse_item_in_loop!(2, category, string, input, builder);
         crate::parse_item_in_loop!(2, cost, "\"cost\"", integer, input, builder);
         crate::parse_item_in_loop!(2, cost, integer, input, builder);
-        crate::parse_item_in_loop!(2, shields, "\"shields\"", integer, input, builder);
-        crate::parse_item_in_loop!(2, hull, "\"hull\"", integer, input, builder);
+        crate::parse_item_in_loop!(2, shields, "\"shields\"", float, input, builder);
+        crate::parse_item_in_loop!(2, hull, "\"hull\"", float, input, builder);
         crate::parse_item_in_loop!(
             2,
             automaton,
@@ -230,16 +234,9 @@ pub fn parse_ship_attributes<'a>(
             input,
             builder
         );
-        crate::parse_item_in_loop!(
-            2,
-            required_crew,
-            "\"required crew\"",
-            integer,
-            input,
-            builder
-        );
-        crate::parse_item_in_loop!(2, bunks, "\"bunks\"", integer, input, builder);
-        crate::parse_item_in_loop!(2, mass, "\"mass\"", integer, input, builder);
+        crate::parse_item_in_loop!(2, required_crew, "\"required crew\"", float, input, builder);
+        crate::parse_item_in_loop!(2, bunks, "\"bunks\"", float, input, builder);
+        crate::parse_item_in_loop!(2, mass, "\"mass\"", float, input, builder);
         crate::parse_item_in_loop!(2, drag, "\"drag\"", float, input, builder);
         crate::parse_item_in_loop!(
             2,
@@ -249,29 +246,30 @@ pub fn parse_ship_attributes<'a>(
             input,
             builder
         );
+        crate::parse_item_in_loop!(2, fuel_capacity, "\"fuel capacity\"", float, input, builder);
+        crate::parse_item_in_loop!(2, cargo_space, "\"cargo space\"", float, input, builder);
+        crate::parse_item_in_loop!(2, outfit_space, "\"outfit space\"", float, input, builder);
         crate::parse_item_in_loop!(
             2,
-            fuel_capacity,
-            "\"fuel capacity\"",
-            integer,
+            weapon_capacity,
+            "\"weapon capacity\"",
+            float,
             input,
             builder
         );
-        crate::parse_item_in_loop!(2, cargo_space, "\"cargo space\"", integer, input, builder);
-        crate::parse_item_in_loop!(2, outfit_space, "\"outfit space\"", integer, input, builder);
         crate::parse_item_in_loop!(
             2,
-            weapon_capacity,
-            "\"weapon capacity\"",
-            integer,
+            engine_capacity,
+            "\"engine capacity\"",
+            float,
             input,
             builder
         );
         crate::parse_item_in_loop!(
             2,
-            engine_capacity,
-            "\"engine capacity\"",
-            integer,
+            maintenance_costs,
+            "\"maintenance costs\"",
+            float,
             input,
             builder
         );
@@ -298,17 +296,10 @@ pub fn parse_ship_weapon<'a>(input: &'a str) -> IResult<&'a str, ShipWeapon, Dat
     let mut builder = crate::types::ShipWeaponBuilder::default();
     let mut input = input;
     loop {
-        crate::parse_item_in_loop!(3, blast_radius, "\"blast radius\"", integer, input, builder);
-        crate::parse_item_in_loop!(
-            3,
-            shield_damage,
-            "\"shield damage\"",
-            integer,
-            input,
-            builder
-        );
-        crate::parse_item_in_loop!(3, hull_damage, "\"hull damage\"", integer, input, builder);
-        crate::parse_item_in_loop!(3, hit_force, "\"hit force\"", integer, input, builder);
+        crate::parse_item_in_loop!(3, blast_radius, "\"blast radius\"", float, input, builder);
+        crate::parse_item_in_loop!(3, shield_damage, "\"shield damage\"", float, input, builder);
+        crate::parse_item_in_loop!(3, hull_damage, "\"hull damage\"", float, input, builder);
+        crate::parse_item_in_loop!(3, hit_force, "\"hit force\"", float, input, builder);
 
         break;
     }
@@ -371,6 +362,7 @@ mod test {
         "outfit space" 100
         "weapon capacity" 0
         "engine capacity" 60
+        "maintenance costs" 250
         weapon
             "blast radius" 10
             "shield damage" 100
@@ -410,24 +402,25 @@ mod test {
                 licenses: vec![],
                 category: "Transport",
                 cost: 100000,
-                shields: 1000,
-                hull: 100,
+                shields: 1000.0,
+                hull: 100.0,
                 automaton: false,
-                required_crew: 1,
-                bunks: 2,
-                mass: 50,
+                required_crew: 1.0,
+                bunks: 2.0,
+                mass: 50.0,
                 drag: 1.0,
                 heat_dissipation: 1.0,
-                fuel_capacity: 500,
-                cargo_space: 20,
-                outfit_space: 100,
-                weapon_capacity: 0,
-                engine_capacity: 60,
+                fuel_capacity: 500.0,
+                cargo_space: 20.0,
+                outfit_space: 100.0,
+                weapon_capacity: 0.0,
+                engine_capacity: 60.0,
+                maintenance_costs: 250.0,
                 weapon: crate::ShipWeapon {
-                    blast_radius: 10,
-                    shield_damage: 100,
-                    hull_damage: 50,
-                    hit_force: 200,
+                    blast_radius: 10.0,
+                    shield_damage: 100.0,
+                    hull_damage: 50.0,
+                    hit_force: 200.0,
                 },
             }
         );
@@ -455,4 +448,30 @@ mod test {
             vec!["My Shuttle.", "   It doesn\'t do much."]
         );
     }
+
+    #[test]
+    fn can_parse_sprite_attributes_in_any_order() {
+        let data = r#""ship/blinking"
+        "no repeat"
+        "frame rate" 12.5
+        "random start frame"
+        "delay" 3
+"#;
+
+        let parsed = dbg!(super::parse_sprite(0, &data));
+        assert!(parsed.is_ok());
+        let (remaining, sprite) = parsed.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            sprite,
+            crate::Sprite::Sprite {
+                name: "ship/blinking",
+                frame_time: None,
+                delay: Some(3),
+                random_start_frame: true,
+                no_repeat: true,
+                frame_rate: Some(12.5),
+            }
+        );
+    }
 }
@@ -4,38 +4,70 @@ use nom::{
     character::complete::{line_ending, space1},
     error::{context, ParseError},
     multi::count,
-    number::complete::float,
-    sequence::tuple,
+    sequence::{preceded, tuple},
     IResult,
 };
 
-use crate::helpers::{date, indent, integer, string};
-use crate::types::{Account, Date, Mortgage, Start};
+use crate::helpers::{date, float, indent, integer, skip_block, string};
+use crate::types::{Account, Mortgage, Start, StartShip};
 
 pub fn parse_start<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Start<'a>, E> {
     let (input, _) = tuple((tag("start"), line_ending))(input)?;
-    let (input, (system, planet, date, set, account)) = permutation((
-        parse_system,
-        parse_planet,
-        parse_date,
-        parse_set,
-        parse_account,
-    ))(input)?;
 
-    Ok((
-        input,
-        Start {
-            date,
-            system,
-            planet,
-            account,
-            set,
-        },
-    ))
+    let mut builder = crate::types::StartBuilder::default();
+    let mut ships = vec![];
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(1, system, string, input, builder);
+        crate::parse_item_in_loop!(1, planet, string, input, builder);
+        crate::parse_item_in_loop!(1, date, date, input, builder);
+        crate::parse_item_in_loop!(1, set, string, input, builder);
+        crate::parse_item_in_loop!(1, account, parse_account, input, builder);
+
+        // ships can be anywhere in the block, and have their own fields
+        if let Ok((remaining, ship)) =
+            preceded(tuple((indent, tag("ship"), space1)), parse_ship::<E>)(input)
+        {
+            ships.push(ship);
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+    builder.ships(ships);
+
+    builder
+        .build()
+        .map(|start| (input, start))
+        .map_err(|_| nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Verify)))
+}
+
+fn parse_ship<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, StartShip<'a>, E> {
+    let (input, (model, _)) = tuple((string, line_ending))(input)?;
+
+    let mut builder = crate::types::StartShipBuilder::default();
+    builder.model(model);
+    let mut input = input;
+    loop {
+        crate::parse_item_in_loop!(2, name, string, input, builder);
+
+        if let Ok((remaining, _)) = skip_block::<E>(2)(input) {
+            input = remaining;
+            continue;
+        }
+
+        break;
+    }
+
+    builder
+        .build()
+        .map(|ship| (input, ship))
+        .map_err(|_| nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Verify)))
 }
 
 fn parse_account<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Account, E> {
-    let (input, _) = tuple((indent, tag("account"), line_ending))(input)?;
+    let (input, _) = line_ending(input)?;
     let (input, (credits, score, mortgage)) =
         permutation((parse_credits, parse_score, parse_mortgage))(input)?;
 
@@ -72,11 +104,6 @@ fn parse_mortgage<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str
     ))
 }
 
-crate::parse_item_with_indent!(1, parse_system, system, string, &'a str);
-crate::parse_item_with_indent!(1, parse_set, set, string, &'a str);
-crate::parse_item_with_indent!(1, parse_planet, planet, string, &'a str);
-crate::parse_item_with_indent!(1, parse_date, date, date, Date);
-
 crate::parse_item_with_indent!(2, parse_credits, credits, integer, u64);
 crate::parse_item_with_indent!(2, parse_score, score, integer, u32);
 
@@ -87,6 +114,7 @@ crate::parse_item_with_indent!(3, parse_term, term, integer, u16);
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::types::Date;
 
     use nom::error::VerboseError;
 
@@ -97,6 +125,8 @@ mod test {
 	planet "this planet"
     date 01 07 2020
 	set "my license"
+	ship "Shuttle"
+		name "Buttercup"
 	account
 		credits 5000
 		score 100
@@ -104,6 +134,7 @@ mod test {
 			principal 33333
 			interest 0.005
             term 365
+	ship "Star Barge"
 "#;
 
         let parsed = dbg!(parse_start::<VerboseError<&str>>(&data));
@@ -120,6 +151,19 @@ mod test {
                 year: 2020
             }
         );
+        assert_eq!(
+            start.ships,
+            vec![
+                StartShip {
+                    model: "Shuttle",
+                    name: Some("Buttercup"),
+                },
+                StartShip {
+                    model: "Star Barge",
+                    name: None,
+                },
+            ]
+        );
         assert_eq!(
             start.account,
             Account {
@@ -6,12 +6,11 @@ use nom::{
     error::{context, ParseError},
     multi::count,
     multi::many0,
-    number::complete::float,
     sequence::tuple,
     IResult,
 };
 
-use crate::helpers::{indent, integer, resource_path, string};
+use crate::helpers::{float, indent, integer, resource_path, string, truncated_float};
 use crate::types::{Asteroids, Fleet, Minables, Position, System, SystemObject, Trade};
 
 pub fn parse_system<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, System<'a>, E> {
@@ -133,7 +132,7 @@ fn parse_trades<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str,
         space1,
         string,
         space1,
-        integer,
+        truncated_float,
         line_ending,
     ))(input)?;
     Ok((input, Trade { name, price }))
@@ -220,6 +219,7 @@ mod test {
     asteroids "large metal" 7 2.345
     minables lead 11 10
     trade Goods 100
+    trade Food 200.5
     fleet "Small Vessel" 100
     object
         sprite planet/visual-planet
@@ -277,10 +277,16 @@ mod test {
         );
         assert_eq!(
             system.trades,
-            vec![Trade {
-                name: "Goods",
-                price: 100
-            }]
+            vec![
+                Trade {
+                    name: "Goods",
+                    price: 100
+                },
+                Trade {
+                    name: "Food",
+                    price: 200
+                }
+            ]
         );
         assert_eq!(
             system.fleets,
@@ -51,9 +51,9 @@ pub struct Fleet<'a> {
 #[builder(setter(into))]
 pub struct Tribute<'a> {
     /// amount given for tribute
-    pub value: u32,
+    pub value: i32,
     /// menace threshold at which planet will reply for tribute
-    pub threshold: u32,
+    pub threshold: i32,
     /// fleet that will protect planet
     pub fleet: Fleet<'a>,
 }
@@ -72,6 +72,20 @@ pub struct Start<'a> {
     pub account: Account,
     /// start set
     pub set: &'a str,
+    /// ships of the player
+    #[builder(default)]
+    pub ships: Vec<StartShip<'a>>,
+}
+
+/// A ship given to the player at the start
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct StartShip<'a> {
+    /// name of the ship model
+    pub model: &'a str,
+    /// name given to the ship
+    #[builder(default)]
+    pub name: Option<&'a str>,
 }
 
 /// A planet
@@ -139,6 +153,53 @@ pub struct Galaxy<'a> {
     pub sprite: Option<&'a str>,
 }
 
+/// A list of ships or outfits sold together
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sale<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// names of the ships or outfits sold
+    pub items: Vec<&'a str>,
+}
+
+/// A color, given by it's components or by the name of a color defined elsewhere
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Color<'a> {
+    /// red, green and blue components, between 0 and 1
+    Rgb(f32, f32, f32),
+    /// name of a color
+    Named(&'a str),
+}
+
+/// A government, that systems, planets and ships belong to
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Government<'a> {
+    /// it's name
+    pub name: &'a str,
+    /// name to display, if different from it's name
+    #[builder(default)]
+    pub display_name: Option<&'a str>,
+    /// color of it's systems on the map
+    #[builder(default)]
+    pub color: Option<Color<'a>>,
+    /// initial reputation of the player with it
+    #[builder(default)]
+    pub player_reputation: Option<f32>,
+    /// attitude toward other governments, negative when hostile
+    #[builder(default)]
+    pub attitudes: Vec<(&'a str, f32)>,
+    /// reputation lost for actions against it's ships, by action
+    #[builder(default)]
+    pub penalties: Vec<(&'a str, f32)>,
+    /// share of the player credits asked as a bribe
+    #[builder(default)]
+    pub bribe: Option<f32>,
+    /// multiplier of the fines it gives
+    #[builder(default)]
+    pub fine: Option<f32>,
+}
+
 /// An asteroid
 #[derive(Debug, PartialEq, Clone, Builder)]
 #[builder(setter(into))]
@@ -170,7 +231,7 @@ pub struct Trade<'a> {
     /// it's name
     pub name: &'a str,
     /// price
-    pub price: u32,
+    pub price: i32,
 }
 
 /// An object in a system
@@ -225,13 +286,13 @@ pub struct System<'a> {
 #[derive(Debug, PartialEq, Clone, Copy, Builder)]
 pub struct ShipWeapon {
     /// it's blast radius
-    pub blast_radius: u32,
+    pub blast_radius: f32,
     /// it's shield damage
-    pub shield_damage: u32,
+    pub shield_damage: f32,
     /// it's hull damage
-    pub hull_damage: u32,
+    pub hull_damage: f32,
     /// it's hit force
-    pub hit_force: u32,
+    pub hit_force: f32,
 }
 
 /// Attributes of a ship
@@ -246,37 +307,40 @@ pub struct ShipAttributes<'a> {
     pub cost: u32,
     /// it's shield
     #[builder(default)]
-    pub shields: u32,
+    pub shields: f32,
     /// it's hull strength
-    pub hull: u32,
+    pub hull: f32,
     /// is it an automaton
     #[builder(default)]
     pub automaton: bool,
     /// it's required crew count
     #[builder(default)]
-    pub required_crew: u32,
+    pub required_crew: f32,
     /// it's bunk count
     #[builder(default)]
-    pub bunks: u32,
+    pub bunks: f32,
     /// it's mass
-    pub mass: u32,
+    pub mass: f32,
     /// it's drag
     pub drag: f32,
     /// it's heat dissipation
     pub heat_dissipation: f32,
     /// it's fuel capacity
     #[builder(default)]
-    pub fuel_capacity: u32,
+    pub fuel_capacity: f32,
     /// it's cargo space
     #[builder(default)]
-    pub cargo_space: u32,
+    pub cargo_space: f32,
     /// it's outfit space
-    pub outfit_space: u32,
+    pub outfit_space: f32,
     /// it's weapon capacity
     #[builder(default)]
-    pub weapon_capacity: u32,
+    pub weapon_capacity: f32,
     /// it's engine capacity
-    pub engine_capacity: u32,
+    pub engine_capacity: f32,
+    /// credits it costs each day
+    #[builder(default)]
+    pub maintenance_costs: f32,
     /// it's weapon (?)
     pub weapon: ShipWeapon,
 }
@@ -358,6 +422,9 @@ pub struct Outfit<'a> {
     /// category of the ouftit
     #[builder(setter(into), default)]
     pub category: Option<&'a str>,
+    /// licences needed to buy this outfit
+    #[builder(default)]
+    pub licenses: Vec<&'a str>,
     /// cost of the outfit
     #[builder(default)]
     pub cost: u32,
@@ -472,6 +539,9 @@ pub struct Outfit<'a> {
     /// map
     #[builder(setter(into), default)]
     pub map: Option<f32>,
+    /// maintenance costs
+    #[builder(setter(into), default)]
+    pub maintenance_costs: Option<f32>,
     /// weapon capacity
     #[builder(setter(into), default)]
     pub weapon_capacity: Option<f32>,
@@ -522,13 +592,19 @@ pub struct Outfit<'a> {
     pub reverse_thrusting_heat: Option<f32>,
     /// energy capacity
     #[builder(setter(into), default)]
-    pub energy_capacity: Option<u32>,
+    pub energy_capacity: Option<f32>,
     /// solar collection
     #[builder(setter(into), default)]
     pub solar_collection: Option<f32>,
     /// energy generation
     #[builder(setter(into), default)]
     pub energy_generation: Option<f32>,
+    /// hull repair rate
+    #[builder(setter(into), default)]
+    pub hull_repair_rate: Option<f32>,
+    /// hull energy
+    #[builder(setter(into), default)]
+    pub hull_energy: Option<f32>,
     /// flare sprite
     #[builder(setter(into), default)]
     pub flare_sprite: Option<Sprite<'a>>,
@@ -573,6 +649,23 @@ pub struct Outfit<'a> {
     pub description: Vec<&'a str>,
 }
 
+/// A submunition created when a projectile dies
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Submunition<'a> {
+    /// name of the outfit used as submunition
+    pub name: &'a str,
+    /// number of submunitions created
+    #[builder(default = "1")]
+    pub count: u32,
+    /// angle of the submunitions relative to the projectile
+    #[builder(default)]
+    pub facing: Option<f32>,
+    /// offset of the submunitions relative to the projectile
+    #[builder(default)]
+    pub offset: Option<Position>,
+}
+
 /// A weapon
 #[derive(Debug, PartialEq, Clone, Builder)]
 pub struct Weapon<'a> {
@@ -584,76 +677,97 @@ pub struct Weapon<'a> {
     pub hardpoint_sprite: Option<Sprite<'a>>,
     /// hardpoint offset
     #[builder(setter(into), default)]
-    hardpoint_offset: Option<Vec<f32>>,
+    pub hardpoint_offset: Option<Vec<f32>>,
     /// sound of the weapon
     #[builder(setter(into), default)]
-    sound: Option<&'a str>,
-    /// ammo used by the weapon
+    pub sound: Option<&'a str>,
+    /// ammo used by the weapon, and how many are used per shot (1 if not specified)
     #[builder(setter(into), default)]
-    ammo: Option<&'a str>,
+    pub ammo: Option<(&'a str, Option<u32>)>,
     /// icon
     #[builder(setter(into), default)]
     pub icon: Option<&'a str>,
     /// hit effect of the weapon
     #[builder(setter(into), default)]
-    hit_effect: Option<(&'a str, Option<i32>)>,
+    pub hit_effect: Option<(&'a str, Option<i32>)>,
     /// fire effect of the weapon
     #[builder(setter(into), default)]
-    fire_effect: Option<(&'a str, Option<i32>)>,
+    pub fire_effect: Option<(&'a str, Option<i32>)>,
     /// die effect of the weapon
     #[builder(setter(into), default)]
-    die_effect: Option<(&'a str, Option<i32>)>,
-    /// submunition of the weapon
+    pub die_effect: Option<(&'a str, Option<i32>)>,
+    /// submunitions of the weapon
     #[builder(setter(into), default)]
-    submunition: Option<(&'a str, Option<i32>)>,
+    pub submunition: Vec<Submunition<'a>>,
     /// anti-missile
     #[builder(setter(into), default)]
-    anti_missile: Option<f32>,
+    pub anti_missile: Option<f32>,
     /// inaccuracy
     #[builder(setter(into), default)]
-    inaccuracy: Option<f32>,
+    pub inaccuracy: Option<f32>,
     /// turret turn
     #[builder(setter(into), default)]
-    turret_turn: Option<f32>,
+    pub turret_turn: Option<f32>,
     /// velocity
     #[builder(setter(into), default)]
-    velocity: Option<f32>,
+    pub velocity: Option<f32>,
     /// lifetime
     #[builder(setter(into), default)]
-    lifetime: Option<f32>,
+    pub lifetime: Option<f32>,
     /// random velocity
     #[builder(setter(into), default)]
-    random_velocity: Option<f32>,
+    pub random_velocity: Option<f32>,
     /// random lifetime
     #[builder(setter(into), default)]
-    random_lifetime: Option<f32>,
-    /// firing energy
+    pub random_lifetime: Option<f32>,
+    /// range used by the AI and the HUD instead of the computed one
+    #[builder(setter(into), default)]
+    pub range_override: Option<f32>,
+    /// reload time
     #[builder(setter(into), default)]
-    reload: Option<f32>,
+    pub reload: Option<f32>,
     /// firing energy
     #[builder(setter(into), default)]
-    firing_energy: Option<f32>,
+    pub firing_energy: Option<f32>,
     /// firing force
     #[builder(setter(into), default)]
-    firing_force: Option<f32>,
+    pub firing_force: Option<f32>,
     /// firing fuel
     #[builder(setter(into), default)]
-    firing_fuel: Option<f32>,
+    pub firing_fuel: Option<f32>,
     /// firing heat
     #[builder(setter(into), default)]
-    firing_heat: Option<f32>,
+    pub firing_heat: Option<f32>,
     /// hit force
     #[builder(setter(into), default)]
-    hit_force: Option<f32>,
+    pub hit_force: Option<f32>,
     /// shield damage
     #[builder(setter(into), default)]
-    shield_damage: Option<f32>,
+    pub shield_damage: Option<f32>,
     /// hull damage
     #[builder(setter(into), default)]
-    hull_damage: Option<f32>,
+    pub hull_damage: Option<f32>,
     /// heat damage
     #[builder(setter(into), default)]
-    heat_damage: Option<f32>,
+    pub heat_damage: Option<f32>,
+    /// fuel damage
+    #[builder(setter(into), default)]
+    pub fuel_damage: Option<f32>,
+    /// energy damage
+    #[builder(setter(into), default)]
+    pub energy_damage: Option<f32>,
+    /// ion damage
+    #[builder(setter(into), default)]
+    pub ion_damage: Option<f32>,
+    /// disruption damage
+    #[builder(setter(into), default)]
+    pub disruption_damage: Option<f32>,
+    /// slowing damage
+    #[builder(setter(into), default)]
+    pub slowing_damage: Option<f32>,
+    /// fraction of the damage that goes through shields
+    #[builder(setter(into), default)]
+    pub piercing: Option<f32>,
     /// acceleration
     #[builder(setter(into), default)]
     pub acceleration: Option<f32>,
@@ -690,6 +804,15 @@ pub struct Weapon<'a> {
     /// clustre
     #[builder(setter(into), default)]
     pub cluster: bool,
+    /// will not damage the ship that fired it
+    #[builder(setter(into), default)]
+    pub safe: bool,
+    /// goes through ships without hitting them until it reaches its target
+    #[builder(setter(into), default)]
+    pub phasing: bool,
+    /// damage is not reduced by the blast radius
+    #[builder(setter(into), default)]
+    pub no_damage_scaling: bool,
     /// burst count
     #[builder(setter(into), default)]
     pub burst_count: Option<u32>,
@@ -728,6 +851,328 @@ pub struct Effect<'a> {
     velocity_scale: Option<f32>,
 }
 
+/// Arithmetic operators usable in a condition expression
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ArithmeticOperator {
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+}
+
+/// Comparison operators usable to test conditions
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ComparisonOperator {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+/// Assignment operators usable to change conditions
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AssignmentOperator {
+    /// `=`
+    Set,
+    /// `+=`
+    Add,
+    /// `-=`
+    Subtract,
+    /// `*=`
+    Multiply,
+    /// `/=`
+    Divide,
+    /// `<?=`, keep the smallest value
+    Min,
+    /// `>?=`, keep the biggest value
+    Max,
+}
+
+/// An arithmetic expression over conditions
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConditionExpr<'a> {
+    /// a literal number
+    Number(i64),
+    /// the value of a condition, 0 if it is not set
+    Condition(&'a str),
+    /// an operation between two expressions
+    Operation(
+        Box<ConditionExpr<'a>>,
+        ArithmeticOperator,
+        Box<ConditionExpr<'a>>,
+    ),
+}
+
+/// A condition, either a test or a change to apply to the player conditions
+#[derive(Debug, PartialEq, Clone)]
+pub enum Condition<'a> {
+    /// compare two expressions
+    Comparison {
+        /// left side of the comparison
+        left: ConditionExpr<'a>,
+        /// comparison operator
+        operator: ComparisonOperator,
+        /// right side of the comparison
+        right: ConditionExpr<'a>,
+    },
+    /// `has "name"`, true if the condition is not 0
+    Has(&'a str),
+    /// `not "name"`, true if the condition is 0
+    Not(&'a str),
+    /// `never`, always false
+    Never,
+    /// `and` block, true if all sub conditions are true
+    And(Vec<Condition<'a>>),
+    /// `or` block, true if any sub condition is true
+    Or(Vec<Condition<'a>>),
+    /// change the value of a condition
+    Assignment {
+        /// name of the condition to change
+        name: &'a str,
+        /// assignment operator
+        operator: AssignmentOperator,
+        /// value used for the assignment
+        value: ConditionExpr<'a>,
+    },
+    /// `"name" ++`
+    Increment(&'a str),
+    /// `"name" --`
+    Decrement(&'a str),
+    /// `set "name"`, set the condition to 1
+    Set(&'a str),
+    /// `clear "name"`, remove the condition
+    Clear(&'a str),
+}
+
+/// A pilot, as saved by Endless Sky
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Pilot<'a> {
+    /// first name of the pilot
+    pub first_name: &'a str,
+    /// last name of the pilot
+    pub last_name: &'a str,
+    /// current date
+    pub date: Date,
+    /// system the pilot is in
+    pub system: &'a str,
+    /// planet the pilot is landed on
+    #[builder(default)]
+    pub planet: Option<&'a str>,
+    /// account of the pilot
+    #[builder(default)]
+    pub account: PilotAccount<'a>,
+    /// ships of the pilot, the first one being the flagship
+    #[builder(default)]
+    pub ships: Vec<PilotShip<'a>>,
+    /// conditions of the pilot, with their value
+    #[builder(default)]
+    pub conditions: Vec<(&'a str, i64)>,
+    /// systems visited by the pilot
+    #[builder(default)]
+    pub visited_systems: Vec<&'a str>,
+    /// planets visited by the pilot
+    #[builder(default)]
+    pub visited_planets: Vec<&'a str>,
+    /// reputation of the pilot with governments
+    #[builder(default)]
+    pub reputations: Vec<(&'a str, f32)>,
+}
+
+/// The account of a pilot
+#[derive(Debug, PartialEq, Clone, Default, Builder)]
+#[builder(setter(into), default)]
+pub struct PilotAccount<'a> {
+    /// how much he currently has
+    pub credits: u64,
+    /// his credit score
+    pub score: u32,
+    /// his mortgages, with their type
+    pub mortgages: Vec<(&'a str, Mortgage)>,
+}
+
+/// A ship owned by a pilot
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct PilotShip<'a> {
+    /// name of the ship model
+    pub model: &'a str,
+    /// name given to the ship
+    #[builder(default)]
+    pub name: Option<&'a str>,
+    /// installed outfits, with their count
+    #[builder(default)]
+    pub outfits: Vec<(&'a str, u32)>,
+    /// fuel left in the ship
+    #[builder(default)]
+    pub fuel: Option<f32>,
+    /// shields left
+    #[builder(default)]
+    pub shields: Option<f32>,
+    /// hull left
+    #[builder(default)]
+    pub hull: Option<f32>,
+    /// system the ship is in
+    #[builder(default)]
+    pub system: Option<&'a str>,
+    /// planet the ship is landed on
+    #[builder(default)]
+    pub planet: Option<&'a str>,
+}
+
+/// Where a mission is offered
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MissionLocation {
+    /// in the spaceport, the default
+    Spaceport,
+    /// as soon as the player lands
+    Landing,
+    /// on the job board
+    Job,
+}
+
+/// Filter on planets, to find where a mission can be offered or where it goes
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LocationFilter<'a> {
+    /// names of the planets allowed
+    pub planets: Vec<&'a str>,
+    /// names of the systems allowed
+    pub systems: Vec<&'a str>,
+    /// names of the governments allowed
+    pub governments: Vec<&'a str>,
+    /// attributes the planet must have, at least one of each list
+    pub attributes: Vec<Vec<&'a str>>,
+}
+
+/// A planet, given by it's name or by a filter
+#[derive(Debug, PartialEq, Clone)]
+pub enum MissionPlace<'a> {
+    /// name of the planet
+    Planet(&'a str),
+    /// filter the planet must match
+    Filter(LocationFilter<'a>),
+}
+
+/// Cargo carried for a mission
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MissionCargo<'a> {
+    /// name of the cargo
+    pub kind: &'a str,
+    /// it's quantity in tons
+    pub tons: u32,
+}
+
+/// Days to complete a mission, each value being optional
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MissionDeadline {
+    /// number of days
+    pub days: Option<u32>,
+    /// days added for each jump to the destination
+    pub multiplier: Option<u32>,
+}
+
+/// Credits paid by a mission, each value being optional
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MissionPayment {
+    /// credits
+    pub base: Option<i64>,
+    /// credits added for each jump to the destination
+    pub multiplier: Option<i64>,
+}
+
+/// When the actions of a mission are run
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MissionTrigger {
+    /// `on offer`
+    Offer,
+    /// `on accept`
+    Accept,
+    /// `on decline`
+    Decline,
+    /// `on complete`
+    Complete,
+    /// `on fail`
+    Fail,
+    /// `on visit`, landing on the destination before the mission can be completed
+    Visit,
+}
+
+/// Actions run by a mission
+#[derive(Debug, PartialEq, Clone)]
+pub struct MissionAction<'a> {
+    /// when they are run
+    pub trigger: MissionTrigger,
+    /// credits paid
+    pub payment: Option<MissionPayment>,
+    /// changes to the player conditions
+    pub conditions: Vec<Condition<'a>>,
+    /// outfits given, or taken when the count is negative
+    pub outfits: Vec<(&'a str, i32)>,
+    /// events triggered, with their delay in days
+    pub events: Vec<(&'a str, Option<u32>)>,
+}
+
+/// A mission
+#[derive(Debug, PartialEq, Clone, Builder)]
+#[builder(setter(into))]
+pub struct Mission<'a> {
+    /// it's identifier
+    pub id: &'a str,
+    /// name shown to the player
+    #[builder(default)]
+    pub name: Option<&'a str>,
+    /// description shown to the player
+    #[builder(default)]
+    pub description: Option<&'a str>,
+    /// where it's offered
+    #[builder(default = "MissionLocation::Spaceport")]
+    pub location: MissionLocation,
+    /// `repeat` line, with the number of times it can be offered
+    #[builder(default)]
+    pub repeat: Option<Option<u32>>,
+    /// days to complete it
+    #[builder(default)]
+    pub deadline: Option<MissionDeadline>,
+    /// cargo to carry
+    #[builder(default)]
+    pub cargo: Option<MissionCargo<'a>>,
+    /// number of passengers to carry
+    #[builder(default)]
+    pub passengers: Option<u32>,
+    /// where it can be offered
+    #[builder(default)]
+    pub source: Option<MissionPlace<'a>>,
+    /// where it must be completed
+    #[builder(default)]
+    pub destination: Option<MissionPlace<'a>>,
+    /// conditions to offer it
+    #[builder(default)]
+    pub to_offer: Vec<Condition<'a>>,
+    /// conditions to complete it
+    #[builder(default)]
+    pub to_complete: Vec<Condition<'a>>,
+    /// conditions that make it fail
+    #[builder(default)]
+    pub to_fail: Vec<Condition<'a>>,
+    /// actions it runs
+    #[builder(default)]
+    pub actions: Vec<MissionAction<'a>>,
+}
+
 /// list of top level objects that can be parsed
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
@@ -746,4 +1191,12 @@ pub enum Object<'a> {
     Outfit(Outfit<'a>),
     /// an effect
     Effect(Effect<'a>),
+    /// ships sold in shipyards
+    Shipyard(Sale<'a>),
+    /// outfits sold in outfitters
+    Outfitter(Sale<'a>),
+    /// a government
+    Government(Government<'a>),
+    /// a mission
+    Mission(Mission<'a>),
 }
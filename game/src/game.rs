@@ -4,14 +4,16 @@ use rand::Rng;
 
 use helpers::{max, min, stringify_fn};
 
+use crate::sprite_frames;
 use crate::square_of_background_stars::{self, SquareOfBackgroundStars};
-use crate::stellar_object::StellarObject;
+use crate::stellar_object::{StellarObject, PLANET_META};
 use crate::universe_map::UniverseMap;
 
 const ZOOM_MIN: f32 = 0.5;
 const ZOOM_MAX: f32 = 5.0;
 const CHANGE_ZOOM_SIGNAL: &str = "change_zoom";
 const BACKGROUND_PARALLAX_SCALE: f32 = 0.2;
+const MAX_LANDING_SPEED: f32 = 50.0;
 
 type OwnerNode = Node2D;
 
@@ -32,6 +34,9 @@ pub struct Game {
 struct Player {
     direction: Angle<f32>,
     speed: Vector2D<f32, UnknownUnit>,
+    sprite: Option<game_data::data::Sprite>,
+    frames: Vec<Texture>,
+    animation_time: f32,
 }
 
 unsafe impl Send for Game {}
@@ -67,6 +72,9 @@ impl Game {
             player: Player {
                 direction: start_direction + Angle::pi(),
                 speed: start_speed,
+                sprite: None,
+                frames: vec![],
+                animation_time: 0.0,
             },
             zoom: 1.0,
             zoom_change: 0.0,
@@ -82,16 +90,27 @@ impl Game {
         let state = state_instance.into_script();
         state
             .map_mut(|state: &mut game_data::State| {
-                let game_data = state.current_game_or_new();
+                let game_data = match state.current_game_or_new() {
+                    Ok(game_data) => game_data,
+                    Err(error) => {
+                        godot_print!("could not start game: {}", error);
+                        unsafe {
+                            owner
+                                .get_tree()
+                                .expect("was able to get tree from node")
+                                .change_scene("res://menu/MainMenu.tscn".into())
+                                .expect("was able to change scene");
+                        }
+                        return;
+                    }
+                };
 
                 unsafe {
                     let map = owner.get_node("universe_map".into());
                     let map_instance: Instance<UniverseMap> =
                         Instance::try_from_unsafe_base(map.unwrap()).unwrap();
                     let map_script = map_instance.into_script();
-                    map_script
-                        .map_mut(|map| map.add_systems(&game_data.systems))
-                        .unwrap();
+                    map_script.map_mut(|map| map.set_game(game_data)).unwrap();
                 }
 
                 let days_since_beginning = game_data.get_nb_days_elapsed_since_beginning() as f32;
@@ -133,21 +152,22 @@ impl Game {
                     .and_then(|player_scene| (&player_scene).instance(0))
                     .and_then(|new_node| unsafe { new_node.cast::<Node2D>() })
                 {
+                    let ship_sprite = game_data.player.flagship().model.sprite.clone();
+                    self.player.frames = sprite_frames::load_frames(&ship_sprite);
+                    if ship_sprite.random_start_frame && ship_sprite.frame_rate > 0.0 {
+                        self.player.animation_time = rand::thread_rng().gen_range(
+                            0.0,
+                            self.player.frames.len() as f32 / ship_sprite.frame_rate,
+                        );
+                    }
+                    self.player.sprite = Some(ship_sprite);
                     unsafe {
-                        let sprite = game_data.player.ship.sprite.clone();
-                        let texture = ResourceLoader::godot_singleton()
-                            .load(
-                                format!("res://images/{}.png", sprite).into(),
-                                "Texture".into(),
-                                false,
-                            )
-                            .and_then(|s| s.cast::<Texture>());
                         let mut sprite = new_player
                             .get_node("Sprite".into())
                             .unwrap()
                             .cast::<Sprite>()
                             .unwrap();
-                        sprite.set_texture(texture);
+                        sprite.set_texture(self.player.frames.first().map(Texture::new_ref));
                         sprite.set_scale(vec2(0.5, 0.5));
                         let mut node = new_player.to_node();
                         node.set_name("player".into());
@@ -156,6 +176,25 @@ impl Game {
                 }
             })
             .unwrap();
+
+        // a new game or a saved one can start landed, the player taking off from the planet
+        let landed = state
+            .map(|state| {
+                state
+                    .current_game
+                    .as_ref()
+                    .map_or(false, |game| game.player.current_planet.is_some())
+            })
+            .unwrap();
+        if landed {
+            unsafe {
+                owner
+                    .get_tree()
+                    .expect("was able to get tree from node")
+                    .change_scene("res://game/Planet.tscn".into())
+                    .expect("was able to change scene");
+            }
+        }
     }
 
     #[export]
@@ -164,9 +203,28 @@ impl Game {
 
         self.player_movement(owner, delta);
 
+        self.animate_player(owner, delta);
+
         self.zoom(owner);
     }
 
+    fn animate_player(&mut self, owner: OwnerNode, delta: f32) {
+        if self.player.frames.len() <= 1 {
+            return;
+        }
+        self.player.animation_time += delta;
+        if let Some(ship_sprite) = self.player.sprite.as_ref() {
+            let frame = ship_sprite.frame_at(self.player.animation_time, self.player.frames.len());
+            unsafe {
+                owner
+                    .get_node("ships/player/Sprite".into())
+                    .and_then(|node| node.cast::<Sprite>())
+                    .unwrap()
+                    .set_texture(self.player.frames.get(frame).map(Texture::new_ref));
+            }
+        }
+    }
+
     fn update_background(&mut self, mut owner: OwnerNode) {
         let view = unsafe { owner.get_viewport().unwrap().get_visible_rect() };
         let mut background_parent = unsafe {
@@ -237,17 +295,24 @@ impl Game {
         let state_instance: Instance<game_data::State> =
             unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
         let state = state_instance.into_script();
-        let (thrust, mass, drag, turn) = state
+        let stats = state
             .map(|state| {
-                let player_ship = state.current_game.as_ref().unwrap().player.ship.as_ref();
-                (
-                    player_ship.get_forward_thrust(),
-                    player_ship.get_mass(),
-                    player_ship.get_drag(),
-                    player_ship.get_turn(),
-                )
+                state
+                    .current_game
+                    .as_ref()
+                    .unwrap()
+                    .player
+                    .flagship()
+                    .stats()
             })
             .unwrap();
+        let (thrust, reverse_thrust, mass, drag, turn) = (
+            stats.forward_thrust,
+            stats.reverse_thrust,
+            stats.mass,
+            stats.drag,
+            stats.turn,
+        );
 
         let es_thrust_ratio = 3600.0;
         let es_turn_ratio = 60.0;
@@ -256,6 +321,7 @@ impl Game {
         let turn_ratio = 0.5;
 
         let acceleration = thrust * es_thrust_ratio / mass * acceleration_ratio;
+        let reverse_acceleration = reverse_thrust * es_thrust_ratio / mass * acceleration_ratio;
         let max_velocity = thrust * es_thrust_ratio / drag * max_velocity_ratio;
 
         let angular_speed = Angle::degrees(turn * es_turn_ratio * turn_ratio / mass);
@@ -268,7 +334,9 @@ impl Game {
         if input.is_action_pressed("ui_left".into()) {
             rotation -= angular_speed * delta;
         }
-        if input.is_action_pressed("ui_down".into()) {
+        if input.is_action_pressed("ui_down".into()) && reverse_thrust > 0.0 {
+            new_acceleration_vec.x += reverse_acceleration;
+        } else if input.is_action_pressed("ui_down".into()) {
             let current_mov_angle = self.player.speed.angle_from_x_axis() + Angle::pi();
             let target = current_mov_angle + Angle::pi();
             let turn = target - rotation;
@@ -303,11 +371,19 @@ impl Game {
             .unwrap();
 
         if input.is_key_pressed(GlobalConstants::KEY_PAGEDOWN)
-            || input.is_mouse_button_pressed(GlobalConstants::BUTTON_WHEEL_UP)
+            || (!self.display_map
+                && input.is_mouse_button_pressed(GlobalConstants::BUTTON_WHEEL_UP))
         {
             self.zoom_change = 0.1;
         }
         if input.is_key_pressed(GlobalConstants::KEY_ESCAPE) {
+            let state_node = unsafe { owner.get_node("/root/State".into()) };
+            let state_instance: Instance<game_data::State> =
+                unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+            let state = state_instance.into_script();
+            if let Err(error) = state.map(|state| state.save_game()).unwrap() {
+                godot_print!("could not save game: {}", error);
+            }
             unsafe {
                 owner
                     .get_tree()
@@ -317,7 +393,8 @@ impl Game {
             }
         }
         if input.is_key_pressed(GlobalConstants::KEY_PAGEUP)
-            || input.is_mouse_button_pressed(GlobalConstants::BUTTON_WHEEL_DOWN)
+            || (!self.display_map
+                && input.is_mouse_button_pressed(GlobalConstants::BUTTON_WHEEL_DOWN))
         {
             self.zoom_change = -0.1;
         }
@@ -351,6 +428,63 @@ impl Game {
             if iek.is_pressed() && !iek.is_echo() && iek.get_scancode() == GlobalConstants::KEY_M {
                 self.toggle_map(owner);
             }
+            if iek.is_pressed() && !iek.is_echo() && iek.get_scancode() == GlobalConstants::KEY_L {
+                self.land(owner);
+            }
+        }
+    }
+
+    fn land(&mut self, owner: OwnerNode) {
+        if self.player.speed.length() > MAX_LANDING_SPEED {
+            godot_print!("going too fast to land");
+            return;
+        }
+        let player = unsafe { owner.get_node("ships/player".into()) }
+            .and_then(|node| unsafe { node.cast::<Area2D>() })
+            .unwrap();
+        let areas = unsafe { player.get_overlapping_areas() };
+        let planet = (0..areas.len())
+            .filter_map(|index| areas.get_ref(index).try_to_object::<Area2D>())
+            .filter(|area| unsafe { area.has_meta(PLANET_META.into()) })
+            .find_map(|area| String::from_variant(&unsafe { area.get_meta(PLANET_META.into()) }));
+        let planet = match planet {
+            Some(planet) => planet,
+            None => {
+                godot_print!("no planet to land on");
+                return;
+            }
+        };
+
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        let state = state_instance.into_script();
+        let landing = state
+            .map_mut(|state| {
+                state
+                    .current_game
+                    .as_mut()
+                    .map(|game| game.land(&planet, &mut rand::thread_rng()))
+            })
+            .unwrap();
+        match landing {
+            Some(Ok(landing)) => {
+                if landing.fine > 0 {
+                    godot_print!("fined {} credits for illegal outfits", landing.fine);
+                }
+                for update in &landing.missions {
+                    godot_print!("mission '{}': {:?}", update.name, update.outcome);
+                }
+                unsafe {
+                    owner
+                        .get_tree()
+                        .expect("was able to get tree from node")
+                        .change_scene("res://game/Planet.tscn".into())
+                        .expect("was able to change scene");
+                }
+            }
+            Some(Err(error)) => godot_print!("could not land on {}: {}", planet, error),
+            None => (),
         }
     }
 
@@ -361,7 +495,10 @@ impl Game {
         let state = state_instance.into_script();
         state
             .map_mut(|state| {
-                let game_data = state.current_game_or_new();
+                let game_data = match state.current_game_or_new() {
+                    Ok(game_data) => game_data,
+                    Err(_) => return,
+                };
                 self.display_map = !self.display_map;
                 unsafe {
                     let mut map = owner.get_node("universe_map".into()).unwrap();
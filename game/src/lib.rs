@@ -17,12 +17,15 @@
 use gdnative::*;
 
 mod game;
+mod planet;
+mod sprite_frames;
 mod square_of_background_stars;
 mod stellar_object;
 mod universe_map;
 
 fn init(handle: gdnative::init::InitHandle) {
     handle.add_class::<game::Game>();
+    handle.add_class::<planet::Planet>();
     handle.add_class::<stellar_object::StellarObject>();
     handle.add_class::<universe_map::UniverseMap>();
     handle.add_class::<square_of_background_stars::SquareOfBackgroundStars>();
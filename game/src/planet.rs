@@ -0,0 +1,122 @@
+use gdnative::*;
+
+use helpers::stringify_fn;
+
+use game_data::missions::MissionLocation;
+
+type OwnerNode = Control;
+
+#[derive(NativeClass)]
+#[inherit(OwnerNode)]
+pub struct Planet {}
+
+unsafe impl Send for Planet {}
+
+#[methods]
+impl Planet {
+    fn _init(_owner: OwnerNode) -> Self {
+        Planet {}
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: OwnerNode) {
+        unsafe {
+            if let Some(mut take_off) = owner.get_node("Panel/TakeOff".into()) {
+                take_off
+                    .connect(
+                        helpers::Signal::Pressed.into(),
+                        Some(owner.to_object()),
+                        stringify_fn!(Self, _take_off),
+                        VariantArray::new(),
+                        0,
+                    )
+                    .expect("signal connected");
+            }
+        }
+
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        let state = state_instance.into_script();
+        state
+            .map(|state| {
+                let planet = match state
+                    .current_game
+                    .as_ref()
+                    .and_then(|game| game.player.current_planet.as_ref())
+                {
+                    Some(planet) => planet,
+                    None => return,
+                };
+                let set_text = |path: &str, text: &str| unsafe {
+                    owner
+                        .get_node(path.into())
+                        .and_then(|node| node.cast::<Label>())
+                        .expect("label is present")
+                        .set_text(text.into());
+                };
+                set_text("Panel/Name", &planet.name);
+                set_text("Panel/Description", &planet.description);
+                set_text("Panel/Spaceport", &planet.spaceport);
+                if let Some(game) = state.current_game.as_ref() {
+                    let mut missions = [
+                        ("landing", MissionLocation::Landing),
+                        ("spaceport", MissionLocation::Spaceport),
+                        ("job", MissionLocation::Job),
+                    ]
+                    .iter()
+                    .flat_map(|(kind, location)| {
+                        game.mission_offers(*location)
+                            .into_iter()
+                            .map(move |offer| {
+                                format!(
+                                    "{}: {} to {}, {} credits",
+                                    kind, offer.name, offer.destination, offer.payment
+                                )
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                    missions.extend(game.player.missions.active().iter().map(|active| {
+                        format!("active: {} to {}", active.name, active.destination)
+                    }));
+                    set_text("Panel/Missions", &missions.join("\n"));
+                }
+
+                if let Some(ref landscape) = planet.landscape {
+                    let texture = ResourceLoader::godot_singleton()
+                        .load(
+                            format!("res://images/{}.png", landscape).into(),
+                            "Texture".into(),
+                            false,
+                        )
+                        .and_then(|texture| texture.cast::<Texture>());
+                    unsafe {
+                        owner
+                            .get_node("Landscape".into())
+                            .and_then(|node| node.cast::<TextureRect>())
+                            .expect("TextureRect Landscape is present")
+                            .set_texture(texture);
+                    }
+                }
+            })
+            .unwrap();
+    }
+
+    #[export]
+    fn _take_off(&mut self, owner: OwnerNode) {
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        state_instance
+            .into_script()
+            .map_mut(|state| state.current_game.as_mut().map(game_data::Game::take_off))
+            .unwrap();
+        unsafe {
+            owner
+                .get_tree()
+                .expect("was able to get tree from node")
+                .change_scene("res://game/Game.tscn".into())
+                .expect("was able to change scene");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use gdnative::*;
+
+use game_data::data::{BlendingMode, Sprite};
+
+fn load_texture(path: &str) -> Option<Texture> {
+    let path = format!("res://images/{}", path);
+    if !ResourceLoader::godot_singleton().exists(path.as_str().into(), "Texture".into()) {
+        return None;
+    }
+    ResourceLoader::godot_singleton()
+        .load(path.into(), "Texture".into(), false)
+        .and_then(|s| s.cast::<Texture>())
+}
+
+/// Load all the frames of a sprite, looking for a single image first then for frames of each blending mode
+pub fn load_frames(sprite: &Sprite) -> Vec<Texture> {
+    if let Some(texture) = load_texture(&sprite.single_frame_path()) {
+        return vec![texture];
+    }
+    BlendingMode::ALL
+        .iter()
+        .map(|blending| {
+            (0..)
+                .map(|frame| load_texture(&sprite.frame_path(*blending, frame)))
+                .take_while(Option::is_some)
+                .flatten()
+                .collect::<Vec<_>>()
+        })
+        .find(|frames| !frames.is_empty())
+        .unwrap_or_default()
+}
@@ -3,6 +3,8 @@ use gdnative::*;
 use helpers::stringify_fn;
 
 const DEFAULT_COLLISION_DIAMETER: f32 = 200.0;
+/// name of the metadata holding the name of the planet a stellar object is
+pub const PLANET_META: &str = "planet";
 
 type OwnerNode = Area2D;
 
@@ -89,9 +91,14 @@ impl StellarObject {
                             .unwrap()
                             .set_disabled(true);
                     }
-                    let rota = euclid::Rotation2D::new(euclid::Angle::radians(
-                        days_since_beginning / object.period * 2.0 * std::f32::consts::PI,
-                    ));
+                    if let (true, Some(planet)) = (object.landable, &object.planet) {
+                        new_stellar_object.set_meta(PLANET_META.into(), planet.to_variant());
+                    }
+                    let rota = euclid::Rotation2D::new(
+                        euclid::Angle::radians(
+                            days_since_beginning / object.period * 2.0 * std::f32::consts::PI,
+                        ) + euclid::Angle::degrees(object.offset),
+                    );
                     let position = euclid::vec2::<f32, euclid::UnknownUnit>(0.0, object.distance);
                     let position = rota.transform_vector(position);
                     new_stellar_object.translate(position);
@@ -110,7 +117,15 @@ impl StellarObject {
     }
 
     #[export]
-    fn _entered_stellar_object(&mut self, _owner: OwnerNode, _entered: Area2D) {
-        godot_print!("over stellar object");
+    fn _entered_stellar_object(&mut self, owner: OwnerNode, _entered: Area2D) {
+        let planet = unsafe {
+            if !owner.has_meta(PLANET_META.into()) {
+                return;
+            }
+            String::from_variant(&owner.get_meta(PLANET_META.into()))
+        };
+        if let Some(planet) = planet {
+            godot_print!("over {}, slow down and press L to land", planet);
+        }
     }
 }
@@ -1,12 +1,42 @@
+use euclid::vec2;
 use gdnative::*;
 
+use helpers::{max, min};
+
+use game_data::navigation::RouteCriteria;
+
+const ZOOM_MIN: f32 = 0.2;
+const ZOOM_MAX: f32 = 5.0;
+const ZOOM_STEP: f32 = 1.25;
+/// distance on screen from a system where a click selects it
+const SELECTION_RADIUS: f32 = 15.0;
+/// distance the mouse can move while the button is pressed for it to still be a click
+const CLICK_DISTANCE: f32 = 5.0;
+const DEFAULT_SYSTEM_COLOR: (f32, f32, f32) = (0.6, 0.6, 0.6);
+/// color of the systems known but never visited
+const UNVISITED_SYSTEM_COLOR: (f32, f32, f32) = (0.3, 0.3, 0.3);
+const LINK_COLOR: Color = Color {
+    r: 0.5,
+    g: 0.5,
+    b: 0.5,
+    a: 0.5,
+};
+
 type OwnerNode = Node;
 
 #[derive(NativeClass)]
 #[inherit(OwnerNode)]
 pub struct UniverseMap {
     system_scene: Option<PackedScene>,
-    systems: Option<Vec<std::sync::Arc<game_data::data::System>>>,
+    systems: Option<Vec<(std::sync::Arc<game_data::data::System>, Color)>>,
+    galaxies: Option<Vec<std::sync::Arc<game_data::data::Galaxy>>>,
+    links: Vec<(Vector2, Vector2)>,
+    visible: bool,
+    zoom: f32,
+    center: Vector2,
+    dragging: bool,
+    drag_distance: f32,
+    selected: Option<String>,
 }
 
 unsafe impl Send for UniverseMap {}
@@ -17,25 +47,56 @@ impl UniverseMap {
         UniverseMap {
             system_scene: helpers::load_scene("res://game/universe_map/system.tscn"),
             systems: None,
+            galaxies: None,
+            links: vec![],
+            visible: false,
+            zoom: 1.0,
+            center: vec2(0.0, 0.0),
+            dragging: false,
+            drag_distance: 0.0,
+            selected: None,
         }
     }
 
     #[export]
     fn _ready(&mut self, owner: OwnerNode) {
-        self.set_map_visibility(owner, false, euclid::vec2(0.0, 0.0));
+        self.set_map_visibility(owner, false, vec2(0.0, 0.0));
     }
 
-    pub fn add_systems(&mut self, systems: &[std::sync::Arc<game_data::data::System>]) {
-        self.systems = Some(systems.to_vec());
+    /// keep the systems known by the player, their links and the galaxies of a game to draw
+    /// them
+    pub fn set_game(&mut self, game: &game_data::Game) {
+        self.systems = Some(
+            game.systems
+                .iter()
+                .filter(|system| game.is_known_system(&system.name))
+                .map(|system| {
+                    let (r, g, b) = if game.is_visited_system(&system.name) {
+                        game.government_color(&system.government)
+                            .unwrap_or(DEFAULT_SYSTEM_COLOR)
+                    } else {
+                        UNVISITED_SYSTEM_COLOR
+                    };
+                    (system.clone(), Color::rgb(r, g, b))
+                })
+                .collect(),
+        );
+        self.links = game
+            .hyperspace_links()
+            .into_iter()
+            .filter(|(first, second)| {
+                game.is_known_system(&first.name) && game.is_known_system(&second.name)
+            })
+            .map(|(first, second)| (first.position, second.position))
+            .collect();
+        self.galaxies = Some(game.galaxies.to_vec());
     }
 
     #[export]
-    pub fn set_map_visibility(
-        &mut self,
-        owner: OwnerNode,
-        visibility: bool,
-        center: euclid::Vector2D<f32, euclid::UnknownUnit>,
-    ) {
+    pub fn set_map_visibility(&mut self, owner: OwnerNode, visibility: bool, center: Vector2) {
+        self.visible = visibility;
+        self.center = center;
+        self.dragging = false;
         unsafe {
             owner
                 .get_node("map/background".into())
@@ -43,26 +104,221 @@ impl UniverseMap {
                 .cast::<ColorRect>()
                 .unwrap()
                 .set_visible(visibility);
+            owner
+                .get_node("map/view".into())
+                .unwrap()
+                .cast::<Node2D>()
+                .unwrap()
+                .set_visible(visibility);
+            owner
+                .get_node("map/selection".into())
+                .unwrap()
+                .cast::<Label>()
+                .unwrap()
+                .set_visible(visibility);
+        }
+        self.update_view(owner);
+        if visibility {
+            // the player may have moved since the route was planned
+            if let Some(selected) = self.selected.clone() {
+                self.select(owner, &selected);
+            }
+        }
+    }
 
-            let mut systems = owner
-                .get_node("map/systems".into())
+    fn update_view(&self, owner: OwnerNode) {
+        unsafe {
+            let mut view = owner
+                .get_node("map/view".into())
                 .unwrap()
                 .cast::<Node2D>()
                 .unwrap();
-            systems.set_visible(visibility);
+            let screen = owner.get_viewport().unwrap().get_visible_rect();
+            view.set_scale(vec2(self.zoom, self.zoom));
+            view.set_position(screen.size.to_vector() / 2.0 - self.center * self.zoom);
+        }
+    }
+
+    fn change_zoom(&mut self, owner: OwnerNode, factor: f32) {
+        self.zoom = max!(min!(self.zoom * factor, ZOOM_MAX), ZOOM_MIN);
+        self.update_view(owner);
+    }
 
-            systems.set_position(-center);
-            let view = owner.get_viewport().unwrap().get_visible_rect();
-            systems.translate(view.size.to_vector() / 2.0);
+    #[export]
+    fn _input(&mut self, owner: OwnerNode, event: InputEvent) {
+        if !self.visible {
+            return;
+        }
+        if let Some(button) = event.cast::<InputEventMouseButton>() {
+            match button.get_button_index() {
+                GlobalConstants::BUTTON_WHEEL_UP if button.is_pressed() => {
+                    self.change_zoom(owner, ZOOM_STEP)
+                }
+                GlobalConstants::BUTTON_WHEEL_DOWN if button.is_pressed() => {
+                    self.change_zoom(owner, 1.0 / ZOOM_STEP)
+                }
+                GlobalConstants::BUTTON_LEFT if button.is_pressed() => {
+                    self.dragging = true;
+                    self.drag_distance = 0.0;
+                }
+                GlobalConstants::BUTTON_LEFT => {
+                    self.dragging = false;
+                    if self.drag_distance < CLICK_DISTANCE {
+                        self.select_at(owner, button.get_position());
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Some(motion) = event.cast::<InputEventMouseMotion>() {
+            if self.dragging {
+                let relative = motion.get_relative();
+                self.drag_distance += relative.length();
+                self.center -= relative / self.zoom;
+                self.update_view(owner);
+            }
+        }
+    }
+
+    fn select_at(&mut self, owner: OwnerNode, screen_position: Vector2) {
+        let view_position = unsafe {
+            owner
+                .get_node("map/view".into())
+                .unwrap()
+                .cast::<Node2D>()
+                .unwrap()
+                .get_position()
+        };
+        let map_position = (screen_position - view_position) / self.zoom;
+        let radius = SELECTION_RADIUS / self.zoom;
+
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        let state = state_instance.into_script();
+        let system = state
+            .map(|state| {
+                state.current_game.as_ref().and_then(|game| {
+                    game.system_at(map_position, radius)
+                        .filter(|system| game.is_known_system(&system.name))
+                        .map(|system| system.name.clone())
+                })
+            })
+            .unwrap();
+        if let Some(system) = system {
+            self.select(owner, &system);
+        }
+    }
+
+    /// Select a system, showing it's details and the route to reach it
+    fn select(&mut self, owner: OwnerNode, name: &str) {
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        let state = state_instance.into_script();
+        let selection = state
+            .map(|state| {
+                let game = state.current_game.as_ref()?;
+                let system = game.systems.iter().find(|system| system.name == name)?;
+                let government = if game.is_visited_system(name) {
+                    game.get_government(&system.government)
+                        .map(|government| government.display_name.clone())
+                        .unwrap_or_else(|| system.government.clone())
+                } else {
+                    String::from("unknown")
+                };
+                let route = game.plan_route(name, RouteCriteria::Jumps);
+                let mut text = format!("{}\ngovernment: {}", system.name, government);
+                let planets = system.planets();
+                if !planets.is_empty() {
+                    text.push_str(&format!("\nplanets: {}", planets.join(", ")));
+                }
+                let mut points = vec![game.player.current_system.position];
+                match &route {
+                    Some(route) => {
+                        text.push_str(&format!("\n{} jumps", route.jumps()));
+                        points.extend(route.steps.iter().filter_map(|step| {
+                            game.systems
+                                .iter()
+                                .find(|system| system.name == step.system)
+                                .map(|system| system.position)
+                        }));
+                    }
+                    None if system.name != game.player.current_system.name => {
+                        text.push_str("\nno route")
+                    }
+                    None => (),
+                }
+                Some((text, points))
+            })
+            .unwrap();
+
+        if let Some((text, points)) = selection {
+            self.selected = Some(String::from(name));
+            unsafe {
+                owner
+                    .get_node("map/selection".into())
+                    .and_then(|node| node.cast::<Label>())
+                    .expect("Label selection is present")
+                    .set_text(text.into());
+                let mut route = owner
+                    .get_node("map/view/route".into())
+                    .and_then(|node| node.cast::<Line2D>())
+                    .expect("Line2D route is present");
+                route.clear_points();
+                if points.len() > 1 {
+                    for point in points {
+                        route.add_point(point, -1);
+                    }
+                }
+            }
         }
     }
 
     #[export]
     fn _process(&mut self, owner: OwnerNode, _delta: f32) {
-        let mut parent = unsafe { owner.get_node("map/systems".into()).unwrap() };
+        let mut galaxy_parent = unsafe { owner.get_node("map/view/galaxies".into()).unwrap() };
+        if unsafe { galaxy_parent.get_child_count() } == 0 {
+            if let Some(galaxies) = &self.galaxies {
+                galaxies
+                    .iter()
+                    .filter_map(|galaxy| galaxy.sprite.as_ref().map(|sprite| (galaxy, sprite)))
+                    .for_each(|(galaxy, sprite)| {
+                        let texture = ResourceLoader::godot_singleton()
+                            .load(
+                                format!("res://images/{}.png", sprite).into(),
+                                "Texture".into(),
+                                false,
+                            )
+                            .and_then(|texture| texture.cast::<Texture>());
+                        let mut new_galaxy = Sprite::new();
+                        unsafe {
+                            new_galaxy.set_texture(texture);
+                            new_galaxy.set_position(galaxy.position);
+                            galaxy_parent.add_child(Some(new_galaxy.to_node()), false);
+                        }
+                    })
+            }
+        }
+
+        let mut link_parent = unsafe { owner.get_node("map/view/links".into()).unwrap() };
+        if unsafe { link_parent.get_child_count() } == 0 {
+            self.links.iter().for_each(|(from, to)| {
+                let mut link = Line2D::new();
+                unsafe {
+                    link.set_default_color(LINK_COLOR);
+                    link.set_width(1.0);
+                    link.add_point(*from, -1);
+                    link.add_point(*to, -1);
+                    link_parent.add_child(Some(link.to_node()), false);
+                }
+            })
+        }
+
+        let mut parent = unsafe { owner.get_node("map/view/systems".into()).unwrap() };
         if unsafe { parent.get_child_count() } == 0 {
             if let Some(systems) = &self.systems {
-                systems.iter().for_each(|system| {
+                systems.iter().for_each(|(system, color)| {
                     if let Some(mut new_system) = self
                         .system_scene
                         .as_ref()
@@ -71,6 +327,12 @@ impl UniverseMap {
                     {
                         unsafe {
                             new_system.translate(system.position);
+                            new_system.set_modulate(*color);
+                            new_system
+                                .get_node("name".into())
+                                .and_then(|node| node.cast::<Label>())
+                                .expect("Label name is present in a system")
+                                .set_text(system.name.as_str().into());
 
                             parent.add_child(Some(new_system.to_node()), false);
                         }
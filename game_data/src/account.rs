@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+
+use helpers::{max, min};
+
+/// daily salary of a crew member, in credits
+pub const CREW_SALARY: i64 = 100;
+/// lowest possible credit score
+pub const MIN_CREDIT_SCORE: i32 = 200;
+/// highest possible credit score
+pub const MAX_CREDIT_SCORE: i32 = 800;
+/// number of days to pay a fine
+pub const FINE_TERM: u32 = 60;
+
+/// What a debt is for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DebtKind {
+    /// a mortgage, taken by the player
+    Mortgage,
+    /// a fine, imposed on the player
+    Fine,
+}
+
+/// A debt, paid back daily
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Mortgage {
+    /// what it's for
+    pub kind: DebtKind,
+    /// amount still owed
+    pub principal: i64,
+    /// daily interest rate
+    pub interest: f64,
+    /// number of days left to pay it back
+    pub term: u32,
+}
+
+impl Mortgage {
+    /// Create a mortgage, with an interest rate depending on the credit score
+    pub fn new(principal: i64, credit_score: i32, term: u32) -> Self {
+        Mortgage {
+            kind: DebtKind::Mortgage,
+            principal,
+            interest: f64::from(600 - credit_score / 2) * 0.00001,
+            term,
+        }
+    }
+
+    /// Create a fine, without interest
+    pub fn fine(amount: i64) -> Self {
+        Mortgage {
+            kind: DebtKind::Fine,
+            principal: amount,
+            interest: 0.0,
+            term: FINE_TERM,
+        }
+    }
+
+    /// Create a mortgage from the one parsed from ES data
+    pub fn from_es_mortgage(mortgage: &es_data_parser::Mortgage) -> Self {
+        Mortgage {
+            kind: DebtKind::Mortgage,
+            principal: mortgage.principal as i64,
+            interest: f64::from(mortgage.interest),
+            term: u32::from(mortgage.term),
+        }
+    }
+
+    /// Amount of the next daily payment, so that the debt is paid back at the end of the term
+    pub fn payment(&self) -> i64 {
+        if self.principal <= 0 {
+            return 0;
+        }
+        if self.term == 0 {
+            return self.principal;
+        }
+        if self.interest == 0.0 {
+            let term = i64::from(self.term);
+            return (self.principal + term - 1) / term;
+        }
+        let power = (1.0 + self.interest).powi(self.term as i32);
+        (self.principal as f64 * self.interest * power / (power - 1.0)).round() as i64
+    }
+
+    /// Make the next daily payment, returning how much was paid
+    pub fn make_payment(&mut self) -> i64 {
+        let payment = self.payment();
+        self.principal -= payment - self.daily_interest();
+        self.term = self.term.saturating_sub(1);
+        payment
+    }
+
+    /// Skip the next daily payment, adding interest to the amount owed
+    pub fn miss_payment(&mut self) {
+        self.principal += self.daily_interest();
+    }
+
+    fn daily_interest(&self) -> i64 {
+        (self.principal as f64 * self.interest).round() as i64
+    }
+}
+
+/// What was paid during a day
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DailyPayments {
+    /// credits paid in crew salaries
+    pub salaries: i64,
+    /// credits paid in maintenance
+    pub maintenance: i64,
+    /// credits paid for mortgages
+    pub mortgages: i64,
+    /// credits paid for fines
+    pub fines: i64,
+    /// if a payment could not be made
+    pub missed_payment: bool,
+}
+
+impl DailyPayments {
+    /// Message to display to the player, if something was paid
+    pub fn message(&self) -> Option<String> {
+        let paid: Vec<String> = [
+            (self.salaries, "in crew salaries"),
+            (self.maintenance, "in maintenance"),
+            (self.mortgages, "in mortgages"),
+            (self.fines, "in fines"),
+        ]
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, reason)| format!("{} credits {}", amount, reason))
+        .collect();
+
+        let mut message = String::new();
+        if self.missed_payment {
+            message.push_str("You could not make all your payments. ");
+        }
+        if !paid.is_empty() {
+            message.push_str(&format!("You paid {}.", paid.join(", ")));
+        }
+        if message.is_empty() {
+            None
+        } else {
+            Some(message.trim_end().to_string())
+        }
+    }
+}
+
+/// Account of a player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    /// credits available
+    pub credits: i64,
+    /// credit score, between `MIN_CREDIT_SCORE` and `MAX_CREDIT_SCORE`
+    pub credit_score: i32,
+    /// mortgages and fines to pay back
+    pub mortgages: Vec<Mortgage>,
+    /// crew salaries that could not be paid yet
+    pub salaries_owed: i64,
+    /// maintenance that could not be paid yet
+    pub maintenance_due: i64,
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Account {
+            credits: 0,
+            credit_score: 400,
+            mortgages: vec![],
+            salaries_owed: 0,
+            maintenance_due: 0,
+        }
+    }
+}
+
+impl Account {
+    /// Create an account from the one parsed from ES data
+    pub fn from_es_account(account: &es_data_parser::Account) -> Self {
+        Account {
+            credits: account.credits as i64,
+            credit_score: account.score as i32,
+            mortgages: if account.mortgage.principal > 0 {
+                vec![Mortgage::from_es_mortgage(&account.mortgage)]
+            } else {
+                vec![]
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Add a fine to pay back
+    pub fn add_fine(&mut self, amount: i64) {
+        self.mortgages.push(Mortgage::fine(amount));
+    }
+
+    /// Total amount still owed for mortgages and fines
+    pub fn total_debt(&self) -> i64 {
+        self.mortgages
+            .iter()
+            .map(|mortgage| mortgage.principal)
+            .sum()
+    }
+
+    /// Pay the debts of a day. Crew salaries are paid first, then maintenance, then each mortgage
+    /// payment is either made in full or missed. The credit score goes up by one each day
+    /// everything was paid, and down by five otherwise.
+    pub fn step(&mut self, salaries: i64, maintenance: i64) -> DailyPayments {
+        let mut payments = DailyPayments::default();
+
+        self.salaries_owed += salaries;
+        payments.salaries = min!(self.salaries_owed, max!(self.credits, 0));
+        self.salaries_owed -= payments.salaries;
+        self.credits -= payments.salaries;
+
+        self.maintenance_due += maintenance;
+        payments.maintenance = min!(self.maintenance_due, max!(self.credits, 0));
+        self.maintenance_due -= payments.maintenance;
+        self.credits -= payments.maintenance;
+
+        payments.missed_payment = self.salaries_owed > 0 || self.maintenance_due > 0;
+
+        for mortgage in self.mortgages.iter_mut() {
+            if mortgage.payment() > self.credits {
+                mortgage.miss_payment();
+                payments.missed_payment = true;
+            } else {
+                let paid = mortgage.make_payment();
+                self.credits -= paid;
+                match mortgage.kind {
+                    DebtKind::Mortgage => payments.mortgages += paid,
+                    DebtKind::Fine => payments.fines += paid,
+                }
+            }
+        }
+        self.mortgages.retain(|mortgage| mortgage.principal > 0);
+
+        self.credit_score = max!(
+            MIN_CREDIT_SCORE,
+            min!(
+                MAX_CREDIT_SCORE,
+                self.credit_score + if payments.missed_payment { -5 } else { 1 }
+            )
+        );
+
+        payments
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_pay_back_a_mortgage_over_its_term() {
+        let mut mortgage = Mortgage::new(480_000, 400, 365);
+        assert!((mortgage.interest - 0.004).abs() < 1e-9);
+        assert_eq!(mortgage.payment(), 2503);
+
+        let mut paid = 0;
+        for _ in 0..365 {
+            paid += mortgage.make_payment();
+        }
+        assert_eq!(mortgage.term, 0);
+        assert!(mortgage.principal.abs() < 365);
+        assert!(paid > 480_000);
+    }
+
+    #[test]
+    fn can_pay_back_a_fine_without_interest() {
+        let mut fine = Mortgage::fine(1000);
+        assert_eq!(fine.payment(), 17);
+        let paid: i64 = (0..FINE_TERM).map(|_| fine.make_payment()).sum();
+        assert_eq!(paid, 1000);
+        assert_eq!(fine.principal, 0);
+    }
+
+    #[test]
+    fn can_pay_daily_finances() {
+        let mut account = Account {
+            credits: 10_000,
+            ..Default::default()
+        };
+        account.mortgages.push(Mortgage::new(100_000, 400, 365));
+        account.add_fine(600);
+
+        let payments = account.step(300, 50);
+        assert_eq!(payments.salaries, 300);
+        assert_eq!(payments.maintenance, 50);
+        assert_eq!(payments.mortgages, 521);
+        assert_eq!(payments.fines, 10);
+        assert!(!payments.missed_payment);
+        assert_eq!(account.credits, 10_000 - 300 - 50 - 521 - 10);
+        assert_eq!(account.credit_score, 401);
+        assert_eq!(
+            payments.message(),
+            Some(String::from(
+                "You paid 300 credits in crew salaries, 50 credits in maintenance, 521 credits in mortgages, 10 credits in fines."
+            ))
+        );
+    }
+
+    #[test]
+    fn will_miss_payments_without_enough_credits() {
+        let mut account = Account {
+            credits: 200,
+            ..Default::default()
+        };
+        account.mortgages.push(Mortgage::new(100_000, 400, 365));
+
+        let payments = account.step(300, 0);
+        assert_eq!(payments.salaries, 200);
+        assert!(payments.missed_payment);
+        assert_eq!(account.credits, 0);
+        assert_eq!(account.salaries_owed, 100);
+        assert_eq!(account.mortgages[0].principal, 100_400);
+        assert_eq!(account.credit_score, 395);
+
+        account.credit_score = MIN_CREDIT_SCORE;
+        account.step(0, 0);
+        assert_eq!(account.credit_score, MIN_CREDIT_SCORE);
+    }
+}
@@ -0,0 +1,441 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use super::data::{Outfit, Player, ShipInstance};
+use super::{Game, TradeError};
+
+/// most a commodity price can drift from it's base price, in credits per ton
+pub const MAX_PRICE_DRIFT: i64 = 100;
+/// most a commodity price can move in a day, in credits per ton
+pub const DAILY_PRICE_DRIFT: i64 = 10;
+
+/// What a ship carries in it's hold. Each ton of cargo uses a unit of cargo space.
+#[derive(Debug, Clone, Default)]
+pub struct Cargo {
+    /// commodities, with their quantity in tons
+    pub commodities: Vec<(String, u32)>,
+    /// cargo of missions, by mission, in tons
+    pub mission_cargo: Vec<(String, u32)>,
+    /// outfits carried instead of being installed, with their count
+    pub outfits: Vec<(Outfit, u32)>,
+}
+
+fn add_to(items: &mut Vec<(String, u32)>, name: &str, count: u32) {
+    match items.iter_mut().find(|(item, _)| item == name) {
+        Some((_, item_count)) => *item_count += count,
+        None => items.push((String::from(name), count)),
+    }
+}
+
+/// remove up to a number of an item, returning how many were removed
+fn remove_from(items: &mut Vec<(String, u32)>, name: &str, count: u32) -> u32 {
+    let mut removed = 0;
+    for (item, item_count) in items.iter_mut() {
+        if item == name {
+            removed = count.min(*item_count);
+            *item_count -= removed;
+        }
+    }
+    items.retain(|(_, count)| *count > 0);
+    removed
+}
+
+fn count_of(items: &[(String, u32)], name: &str) -> u32 {
+    items
+        .iter()
+        .find(|(item, _)| item == name)
+        .map(|(_, count)| *count)
+        .unwrap_or(0)
+}
+
+impl Cargo {
+    /// mass of everything in the hold, which is also the cargo space it uses
+    pub fn mass(&self) -> f32 {
+        let tons: u32 = self
+            .commodities
+            .iter()
+            .chain(self.mission_cargo.iter())
+            .map(|(_, tons)| tons)
+            .sum();
+        tons as f32
+            + self
+                .outfits
+                .iter()
+                .map(|(outfit, count)| outfit.mass * (*count as f32))
+                .sum::<f32>()
+    }
+
+    /// is the hold empty
+    pub fn is_empty(&self) -> bool {
+        self.commodities.is_empty() && self.mission_cargo.is_empty() && self.outfits.is_empty()
+    }
+
+    /// get the tons of a commodity in the hold
+    pub fn get_commodity(&self, name: &str) -> u32 {
+        count_of(&self.commodities, name)
+    }
+
+    /// add tons of a commodity
+    pub fn add_commodity(&mut self, name: &str, tons: u32) {
+        add_to(&mut self.commodities, name, tons);
+    }
+
+    /// remove up to a number of tons of a commodity, returning how many were removed
+    pub fn remove_commodity(&mut self, name: &str, tons: u32) -> u32 {
+        remove_from(&mut self.commodities, name, tons)
+    }
+
+    /// get the tons of cargo of a mission in the hold
+    pub fn get_mission_cargo(&self, mission: &str) -> u32 {
+        count_of(&self.mission_cargo, mission)
+    }
+
+    /// add tons of cargo of a mission
+    pub fn add_mission_cargo(&mut self, mission: &str, tons: u32) {
+        add_to(&mut self.mission_cargo, mission, tons);
+    }
+
+    /// remove all the cargo of a mission, returning how many tons were removed
+    pub fn remove_mission_cargo(&mut self, mission: &str) -> u32 {
+        remove_from(&mut self.mission_cargo, mission, u32::MAX)
+    }
+
+    /// get the number of an outfit in the hold
+    pub fn get_outfit(&self, name: &str) -> u32 {
+        self.outfits
+            .iter()
+            .find(|(outfit, _)| outfit.name == name)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    /// add a number of an outfit
+    pub fn add_outfit(&mut self, outfit: &Outfit, count: u32) {
+        match self
+            .outfits
+            .iter_mut()
+            .find(|(carried, _)| carried.name == outfit.name)
+        {
+            Some((_, carried_count)) => *carried_count += count,
+            None => self.outfits.push((outfit.clone(), count)),
+        }
+    }
+
+    /// remove up to a number of an outfit, returning how many were removed
+    pub fn remove_outfit(&mut self, name: &str, count: u32) -> u32 {
+        let mut removed = 0;
+        for (outfit, carried_count) in self.outfits.iter_mut() {
+            if outfit.name == name {
+                removed = count.min(*carried_count);
+                *carried_count -= removed;
+            }
+        }
+        self.outfits.retain(|(_, count)| *count > 0);
+        removed
+    }
+}
+
+impl ShipInstance {
+    /// cargo space left, once outfits and cargo are in
+    pub fn free_cargo_space(&self) -> f32 {
+        self.stats().cargo_space.free() - self.cargo.mass()
+    }
+}
+
+impl Player {
+    /// indexes of the ships carrying cargo for the player, the flagship first
+    fn cargo_ships(&self) -> Vec<usize> {
+        std::iter::once(self.flagship)
+            .chain(self.escorts().map(|(index, _)| index))
+            .collect()
+    }
+
+    /// tons of cargo space left in the flagship and it's escorts
+    pub fn free_cargo_space(&self) -> u32 {
+        self.cargo_ships()
+            .into_iter()
+            .map(|index| self.fleet[index].free_cargo_space().floor().max(0.0) as u32)
+            .sum()
+    }
+
+    /// tons of a commodity in the flagship and it's escorts
+    pub fn get_commodity(&self, name: &str) -> u32 {
+        self.cargo_ships()
+            .into_iter()
+            .map(|index| self.fleet[index].cargo.get_commodity(name))
+            .sum()
+    }
+
+    /// add tons of a commodity, filling the flagship first then it's escorts. Returns the tons
+    /// that didn't fit.
+    pub fn add_commodity(&mut self, name: &str, tons: u32) -> u32 {
+        let mut left = tons;
+        for index in self.cargo_ships() {
+            let ship = &mut self.fleet[index];
+            let loaded = left.min(ship.free_cargo_space().floor().max(0.0) as u32);
+            if loaded > 0 {
+                ship.cargo.add_commodity(name, loaded);
+                left -= loaded;
+            }
+        }
+        left
+    }
+
+    /// remove up to a number of tons of a commodity from the flagship and it's escorts,
+    /// returning how many were removed
+    pub fn remove_commodity(&mut self, name: &str, tons: u32) -> u32 {
+        let mut removed = 0;
+        for index in self.cargo_ships() {
+            removed += self.fleet[index]
+                .cargo
+                .remove_commodity(name, tons - removed);
+        }
+        removed
+    }
+
+    /// tons of cargo of a mission in the flagship and it's escorts
+    pub fn get_mission_cargo(&self, mission: &str) -> u32 {
+        self.cargo_ships()
+            .into_iter()
+            .map(|index| self.fleet[index].cargo.get_mission_cargo(mission))
+            .sum()
+    }
+
+    /// add tons of cargo of a mission, filling the flagship first then it's escorts. Returns
+    /// the tons that didn't fit.
+    pub fn add_mission_cargo(&mut self, mission: &str, tons: u32) -> u32 {
+        let mut left = tons;
+        for index in self.cargo_ships() {
+            let ship = &mut self.fleet[index];
+            let loaded = left.min(ship.free_cargo_space().floor().max(0.0) as u32);
+            if loaded > 0 {
+                ship.cargo.add_mission_cargo(mission, loaded);
+                left -= loaded;
+            }
+        }
+        left
+    }
+
+    /// remove all the cargo of a mission from the whole fleet, returning how many tons were
+    /// removed
+    pub fn remove_mission_cargo(&mut self, mission: &str) -> u32 {
+        self.fleet
+            .iter_mut()
+            .map(|ship| ship.cargo.remove_mission_cargo(mission))
+            .sum()
+    }
+
+    /// bunks left for passengers in the flagship and it's escorts, once their crew is on board
+    pub fn free_bunks(&self) -> u32 {
+        let bunks: u32 = self
+            .cargo_ships()
+            .into_iter()
+            .map(|index| self.fleet[index].stats().free_bunks().floor().max(0.0) as u32)
+            .sum();
+        bunks.saturating_sub(self.missions.passengers())
+    }
+}
+
+impl Game {
+    /// price of a ton of a commodity in the current system, if it's traded there
+    pub fn commodity_price(&self, name: &str) -> Option<i64> {
+        self.player
+            .current_system
+            .trades
+            .iter()
+            .find(|trade| trade.commodity == name)
+            .map(|trade| trade.price)
+    }
+
+    /// Move the commodity prices of every system by a random amount, keeping them near their base
+    /// price
+    pub fn drift_markets<R: Rng>(&mut self, rng: &mut R) {
+        for system in self.systems.iter_mut() {
+            if system.trades.is_empty() {
+                continue;
+            }
+            for trade in Arc::make_mut(system).trades.iter_mut() {
+                let drift = rng.gen_range(-DAILY_PRICE_DRIFT, DAILY_PRICE_DRIFT + 1);
+                trade.price = (trade.price + drift)
+                    .max(trade.base_price - MAX_PRICE_DRIFT)
+                    .min(trade.base_price + MAX_PRICE_DRIFT);
+            }
+        }
+        if let Some(system) = self
+            .systems
+            .iter()
+            .find(|system| system.name == self.player.current_system.name)
+        {
+            self.player.current_system = system.clone();
+        }
+    }
+
+    fn landed_commodity_price(&self, name: &str) -> Result<i64, TradeError> {
+        if self.player.current_planet.is_none() {
+            return Err(TradeError::NotLanded);
+        }
+        self.commodity_price(name)
+            .ok_or_else(|| TradeError::NotSold(String::from(name)))
+    }
+
+    /// Buy tons of a commodity at the price of the current system. Returns the credits paid.
+    pub fn buy_commodity(&mut self, name: &str, tons: u32) -> Result<i64, TradeError> {
+        let price = self.landed_commodity_price(name)? * i64::from(tons);
+        let available = self.player.free_cargo_space();
+        if tons > available {
+            return Err(TradeError::NotEnoughCargoSpace {
+                needed: tons,
+                available,
+            });
+        }
+        self.pay(price)?;
+        self.player.add_commodity(name, tons);
+        Ok(price)
+    }
+
+    /// Sell tons of a commodity at the price of the current system. Returns the credits
+    /// received.
+    pub fn sell_commodity(&mut self, name: &str, tons: u32) -> Result<i64, TradeError> {
+        let price = self.landed_commodity_price(name)? * i64::from(tons);
+        let available = self.player.get_commodity(name);
+        if tons > available {
+            return Err(TradeError::NotEnoughCargo {
+                item: String::from(name),
+                available,
+            });
+        }
+        self.player.remove_commodity(name, tons);
+        self.player.account.credits += price;
+        Ok(price)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::mock::StepRng;
+
+    use super::DAILY_PRICE_DRIFT;
+    use crate::test_data;
+    use crate::{Game, TradeError};
+
+    const DATA: &str = r#"outfit "Cargo Pod"
+	category "Systems"
+	"mass" 5
+	"outfit space" -5
+	"cargo space" 10
+"#;
+
+    const TRADES: &str = "\ttrade Food 200\n\ttrade Metal 400\n";
+
+    fn game() -> Game {
+        let system = format!("{}{}", test_data::SYSTEM, TRADES);
+        let mut game = test_data::game_from(&[
+            test_data::SHIPS,
+            test_data::PLANET,
+            &system,
+            test_data::START,
+            DATA,
+        ]);
+        let escort = game.player.flagship().clone();
+        game.player.fleet.push(escort);
+        game
+    }
+
+    #[test]
+    fn can_buy_commodities_within_cargo_space_and_credits() {
+        let mut game = game();
+        assert_eq!(game.player.free_cargo_space(), 40);
+        let mass = game.player.flagship().get_mass();
+
+        assert_eq!(
+            game.buy_commodity("Gold", 1),
+            Err(TradeError::NotSold(String::from("Gold")))
+        );
+        assert_eq!(
+            game.buy_commodity("Food", 41),
+            Err(TradeError::NotEnoughCargoSpace {
+                needed: 41,
+                available: 40,
+            })
+        );
+        assert_eq!(
+            game.buy_commodity("Metal", 30),
+            Err(TradeError::NotEnoughCredits {
+                needed: 12_000,
+                available: 10_000,
+            })
+        );
+        assert_eq!(game.player.account.credits, 10_000);
+
+        assert_eq!(game.buy_commodity("Food", 30), Ok(6000));
+        assert_eq!(game.player.account.credits, 4000);
+        assert_eq!(game.player.fleet[0].cargo.get_commodity("Food"), 20);
+        assert_eq!(game.player.fleet[1].cargo.get_commodity("Food"), 10);
+        assert_eq!(game.player.flagship().get_mass(), mass + 20.0);
+        assert_eq!(game.player.flagship().stats().mass, mass + 20.0);
+        assert_eq!(game.player.free_cargo_space(), 10);
+
+        game.player.current_planet = None;
+        assert_eq!(game.buy_commodity("Food", 1), Err(TradeError::NotLanded));
+    }
+
+    #[test]
+    fn can_sell_commodities_from_the_whole_fleet() {
+        let mut game = game();
+        game.buy_commodity("Food", 30).unwrap();
+
+        assert_eq!(
+            game.sell_commodity("Food", 31),
+            Err(TradeError::NotEnoughCargo {
+                item: String::from("Food"),
+                available: 30,
+            })
+        );
+        assert_eq!(game.sell_commodity("Food", 25), Ok(5000));
+        assert_eq!(game.player.get_commodity("Food"), 5);
+        assert_eq!(game.player.fleet[0].cargo.get_commodity("Food"), 0);
+        assert_eq!(game.player.account.credits, 4000 + 5000);
+        assert_eq!(game.player.free_cargo_space(), 35);
+    }
+
+    #[test]
+    fn can_carry_mission_cargo_and_outfits() {
+        let game = game();
+        let mut ship = game.player.flagship().clone();
+        let cargo_pod = game
+            .outfits
+            .iter()
+            .find(|outfit| outfit.name == "Cargo Pod")
+            .unwrap()
+            .clone();
+
+        ship.cargo.add_mission_cargo("Delivery to Away", 8);
+        ship.cargo.add_outfit(&cargo_pod, 2);
+        assert_eq!(ship.cargo.mass(), 18.0);
+        assert_eq!(ship.free_cargo_space(), 2.0);
+
+        assert_eq!(ship.cargo.remove_outfit("Cargo Pod", 3), 2);
+        assert_eq!(ship.cargo.remove_mission_cargo("Delivery to Away"), 8);
+        assert!(ship.cargo.is_empty());
+    }
+
+    #[test]
+    fn can_drift_commodity_prices_around_their_base_price() {
+        let mut game = game();
+        for _ in 0..5 {
+            game.drift_markets(&mut StepRng::new(0, 0));
+        }
+        assert_eq!(game.commodity_price("Food"), Some(150));
+        assert_eq!(game.commodity_price("Metal"), Some(350));
+        for _ in 0..10 {
+            game.drift_markets(&mut StepRng::new(0, 0));
+        }
+        assert_eq!(game.commodity_price("Food"), Some(100));
+        assert_eq!(game.systems[0].trades[0].base_price, 200);
+
+        game.advance_days(1);
+        let price = game.commodity_price("Food").unwrap();
+        assert!((100..=100 + DAILY_PRICE_DRIFT).contains(&price));
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+pub use es_data_parser::{ArithmeticOperator, AssignmentOperator, ComparisonOperator};
+
+/// An arithmetic expression over conditions
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionExpr {
+    /// a literal number
+    Number(i64),
+    /// the value of a condition, 0 if it is not set
+    Condition(String),
+    /// an operation between two expressions
+    Operation(Box<ConditionExpr>, ArithmeticOperator, Box<ConditionExpr>),
+}
+
+impl ConditionExpr {
+    /// create an expression from ES data
+    pub fn from_es_expr(expr: &es_data_parser::ConditionExpr) -> Self {
+        match expr {
+            es_data_parser::ConditionExpr::Number(value) => ConditionExpr::Number(*value),
+            es_data_parser::ConditionExpr::Condition(name) => {
+                ConditionExpr::Condition(String::from(*name))
+            }
+            es_data_parser::ConditionExpr::Operation(left, operator, right) => {
+                ConditionExpr::Operation(
+                    Box::new(Self::from_es_expr(left)),
+                    *operator,
+                    Box::new(Self::from_es_expr(right)),
+                )
+            }
+        }
+    }
+}
+
+/// A condition, either a test or a change to apply to the player conditions
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// compare two expressions
+    Comparison {
+        /// left side of the comparison
+        left: ConditionExpr,
+        /// comparison operator
+        operator: ComparisonOperator,
+        /// right side of the comparison
+        right: ConditionExpr,
+    },
+    /// true if the condition is not 0
+    Has(String),
+    /// true if the condition is 0
+    Not(String),
+    /// always false
+    Never,
+    /// true if all sub conditions are true
+    And(Vec<Condition>),
+    /// true if any sub condition is true
+    Or(Vec<Condition>),
+    /// change the value of a condition
+    Assignment {
+        /// name of the condition to change
+        name: String,
+        /// assignment operator
+        operator: AssignmentOperator,
+        /// value used for the assignment
+        value: ConditionExpr,
+    },
+    /// add 1 to a condition
+    Increment(String),
+    /// remove 1 from a condition
+    Decrement(String),
+    /// set a condition to 1
+    Set(String),
+    /// remove a condition
+    Clear(String),
+}
+
+impl Condition {
+    /// create a condition from ES data
+    pub fn from_es_condition(condition: &es_data_parser::Condition) -> Self {
+        match condition {
+            es_data_parser::Condition::Comparison {
+                left,
+                operator,
+                right,
+            } => Condition::Comparison {
+                left: ConditionExpr::from_es_expr(left),
+                operator: *operator,
+                right: ConditionExpr::from_es_expr(right),
+            },
+            es_data_parser::Condition::Has(name) => Condition::Has(String::from(*name)),
+            es_data_parser::Condition::Not(name) => Condition::Not(String::from(*name)),
+            es_data_parser::Condition::Never => Condition::Never,
+            es_data_parser::Condition::And(conditions) => {
+                Condition::And(conditions.iter().map(Self::from_es_condition).collect())
+            }
+            es_data_parser::Condition::Or(conditions) => {
+                Condition::Or(conditions.iter().map(Self::from_es_condition).collect())
+            }
+            es_data_parser::Condition::Assignment {
+                name,
+                operator,
+                value,
+            } => Condition::Assignment {
+                name: String::from(*name),
+                operator: *operator,
+                value: ConditionExpr::from_es_expr(value),
+            },
+            es_data_parser::Condition::Increment(name) => Condition::Increment(String::from(*name)),
+            es_data_parser::Condition::Decrement(name) => Condition::Decrement(String::from(*name)),
+            es_data_parser::Condition::Set(name) => Condition::Set(String::from(*name)),
+            es_data_parser::Condition::Clear(name) => Condition::Clear(String::from(*name)),
+        }
+    }
+
+    /// is this condition a change to apply instead of a test
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            self,
+            Condition::Assignment { .. }
+                | Condition::Increment(_)
+                | Condition::Decrement(_)
+                | Condition::Set(_)
+                | Condition::Clear(_)
+        )
+    }
+}
+
+/// Values of the player conditions, a condition that is not set is 0
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionStore {
+    values: HashMap<String, i64>,
+}
+
+impl ConditionStore {
+    /// create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// get the value of a condition
+    pub fn get(&self, name: &str) -> i64 {
+        self.values.get(name).copied().unwrap_or(0)
+    }
+
+    /// set the value of a condition
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.insert(String::from(name), value);
+    }
+
+    /// remove a condition
+    pub fn clear(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+
+    /// iterate over all conditions that are set
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &i64)> {
+        self.values.iter()
+    }
+
+    /// update the `day`, `month` and `year` conditions
+    pub fn set_date(&mut self, date: chrono::NaiveDate) {
+        use chrono::Datelike;
+        self.set("day", i64::from(date.day()));
+        self.set("month", i64::from(date.month()));
+        self.set("year", i64::from(date.year()));
+    }
+
+    /// compute the value of an expression
+    pub fn evaluate(&self, expr: &ConditionExpr) -> i64 {
+        match expr {
+            ConditionExpr::Number(value) => *value,
+            ConditionExpr::Condition(name) => self.get(name),
+            ConditionExpr::Operation(left, operator, right) => {
+                let left = self.evaluate(left);
+                let right = self.evaluate(right);
+                match operator {
+                    ArithmeticOperator::Add => left.saturating_add(right),
+                    ArithmeticOperator::Subtract => left.saturating_sub(right),
+                    ArithmeticOperator::Multiply => left.saturating_mul(right),
+                    // ES uses the biggest value when dividing by 0
+                    ArithmeticOperator::Divide => left.checked_div(right).unwrap_or(i64::MAX),
+                    ArithmeticOperator::Modulo => left.checked_rem(right).unwrap_or(left),
+                }
+            }
+        }
+    }
+
+    /// check if a condition is true, changes are always true
+    pub fn test(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Comparison {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.evaluate(left);
+                let right = self.evaluate(right);
+                match operator {
+                    ComparisonOperator::Equal => left == right,
+                    ComparisonOperator::NotEqual => left != right,
+                    ComparisonOperator::Less => left < right,
+                    ComparisonOperator::LessOrEqual => left <= right,
+                    ComparisonOperator::Greater => left > right,
+                    ComparisonOperator::GreaterOrEqual => left >= right,
+                }
+            }
+            Condition::Has(name) => self.get(name) != 0,
+            Condition::Not(name) => self.get(name) == 0,
+            Condition::Never => false,
+            Condition::And(conditions) => self.test_all(conditions),
+            Condition::Or(conditions) => conditions
+                .iter()
+                .filter(|condition| !condition.is_assignment())
+                .any(|condition| self.test(condition)),
+            _ => true,
+        }
+    }
+
+    /// check if all conditions of a list are true
+    pub fn test_all(&self, conditions: &[Condition]) -> bool {
+        conditions.iter().all(|condition| self.test(condition))
+    }
+
+    /// apply a change to the conditions, tests are ignored
+    pub fn apply(&mut self, condition: &Condition) {
+        match condition {
+            Condition::Assignment {
+                name,
+                operator,
+                value,
+            } => {
+                let current = self.get(name);
+                let value = self.evaluate(value);
+                let new_value = match operator {
+                    AssignmentOperator::Set => value,
+                    AssignmentOperator::Add => current.saturating_add(value),
+                    AssignmentOperator::Subtract => current.saturating_sub(value),
+                    AssignmentOperator::Multiply => current.saturating_mul(value),
+                    AssignmentOperator::Divide => current.checked_div(value).unwrap_or(i64::MAX),
+                    AssignmentOperator::Min => current.min(value),
+                    AssignmentOperator::Max => current.max(value),
+                };
+                self.set(name, new_value);
+            }
+            Condition::Increment(name) => self.set(name, self.get(name).saturating_add(1)),
+            Condition::Decrement(name) => self.set(name, self.get(name).saturating_sub(1)),
+            Condition::Set(name) => self.set(name, 1),
+            Condition::Clear(name) => self.clear(name),
+            _ => (),
+        }
+    }
+
+    /// apply all changes of a list
+    pub fn apply_all(&mut self, conditions: &[Condition]) {
+        conditions
+            .iter()
+            .for_each(|condition| self.apply(condition));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn conditions(data: &str) -> Vec<Condition> {
+        es_data_parser::parse_conditions(data)
+            .iter()
+            .map(Condition::from_es_condition)
+            .collect()
+    }
+
+    #[test]
+    fn can_test_conditions() {
+        let mut store = ConditionStore::new();
+        store.set("combat rating", 150);
+        store.set("event: foo", 1);
+
+        assert!(store.test_all(&conditions(
+            r#""combat rating" > 100
+has "event: foo"
+not "x"
+"combat rating" - 50 * 2 == 50
+"#
+        )));
+        assert!(!store.test_all(&conditions("never\n")));
+        assert!(!store.test_all(&conditions("has \"x\"\n")));
+        assert!(store.test_all(&conditions(
+            r#"or
+    has "x"
+    "combat rating" >= 150
+"#
+        )));
+        assert!(!store.test_all(&conditions(
+            r#"and
+    has "x"
+    "combat rating" >= 150
+"#
+        )));
+    }
+
+    #[test]
+    fn can_apply_changes() {
+        let mut store = ConditionStore::new();
+        store.apply_all(&conditions(
+            r#""x" += 2
+"x" *= 5
+"y" = "x" / 3
+"z" ++
+"z" ++
+"z" --
+"w" >?= 7
+"w" <?= 4
+set "flag"
+set "other flag"
+clear "other flag"
+has "test is ignored"
+"#,
+        ));
+        assert_eq!(store.get("x"), 10);
+        assert_eq!(store.get("y"), 3);
+        assert_eq!(store.get("z"), 1);
+        assert_eq!(store.get("w"), 4);
+        assert_eq!(store.get("flag"), 1);
+        assert_eq!(store.iter().count(), 5);
+    }
+
+    #[test]
+    fn can_read_date_conditions() {
+        let mut store = ConditionStore::new();
+        store.set_date(chrono::NaiveDate::from_ymd(3013, 11, 16));
+        assert!(store.test_all(&conditions(
+            r#""year" == 3013
+"month" >= 11
+"day" < 20
+"#
+        )));
+    }
+}
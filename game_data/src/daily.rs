@@ -0,0 +1,166 @@
+use std::fmt;
+
+use super::Game;
+
+/// Something that happened during a day
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// the day it happened
+    pub date: chrono::NaiveDate,
+    /// what happened
+    pub message: String,
+}
+
+/// A system run once for each day that passes, returning messages for the log
+#[derive(Clone, Copy)]
+pub struct DailySystem {
+    /// it's name
+    pub name: &'static str,
+    /// the function to run
+    pub run: fn(&mut Game) -> Vec<String>,
+}
+
+impl fmt::Debug for DailySystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DailySystem")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Systems run each day in a new game
+pub fn default_daily_systems() -> Vec<DailySystem> {
+    vec![
+        DailySystem {
+            name: "date",
+            run: update_date_conditions,
+        },
+        DailySystem {
+            name: "markets",
+            run: drift_markets,
+        },
+        DailySystem {
+            name: "finances",
+            run: pay_finances,
+        },
+        DailySystem {
+            name: "missions",
+            run: update_missions,
+        },
+    ]
+}
+
+fn update_date_conditions(game: &mut Game) -> Vec<String> {
+    game.conditions.set_date(game.current_date);
+    vec![]
+}
+
+fn drift_markets(game: &mut Game) -> Vec<String> {
+    game.drift_markets(&mut rand::thread_rng());
+    vec![]
+}
+
+fn pay_finances(game: &mut Game) -> Vec<String> {
+    game.pay_daily_finances().message().into_iter().collect()
+}
+
+fn update_missions(game: &mut Game) -> Vec<String> {
+    game.trigger_due_events();
+    game.fail_late_missions()
+}
+
+impl Game {
+    /// Add a system that will be run each day, after the ones already registered
+    pub fn register_daily_system(&mut self, name: &'static str, run: fn(&mut Game) -> Vec<String>) {
+        self.daily_systems.push(DailySystem { name, run });
+    }
+
+    /// Move the date forward by a number of days, running the daily systems for each day. Time
+    /// stops on the last date that can be represented.
+    pub fn advance_days(&mut self, days: u32) -> Vec<LogEntry> {
+        let mut log = vec![];
+        for _ in 0..days {
+            self.current_date = match self.current_date.succ_opt() {
+                Some(date) => date,
+                None => break,
+            };
+            let daily_systems = self.daily_systems.clone();
+            for daily_system in daily_systems {
+                let date = self.current_date;
+                log.extend(
+                    (daily_system.run)(self)
+                        .into_iter()
+                        .map(|message| LogEntry { date, message }),
+                );
+            }
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::data::{Attributes, ShipModel};
+    use crate::test_data;
+
+    /// a game with 1000 credits and a shuttle needing a crew of 3, paid 200 credits a day
+    fn game() -> Game {
+        let mut game = test_data::game_with("");
+        game.player.account.credits = 1000;
+        game.player.fleet[0].model = Arc::new(ShipModel {
+            attributes: Attributes {
+                required_crew: 3.0,
+                ..game.player.fleet[0].model.attributes
+            },
+            ..(*game.player.fleet[0].model).clone()
+        });
+        game
+    }
+
+    #[test]
+    fn can_advance_days() {
+        let mut game = game();
+        let log = game.advance_days(15);
+
+        assert_eq!(game.current_date, chrono::NaiveDate::from_ymd(3013, 12, 1));
+        assert_eq!(game.conditions.get("day"), 1);
+        assert_eq!(game.conditions.get("month"), 12);
+        assert_eq!(game.player.account.credits, 0);
+        assert_eq!(game.player.account.salaries_owed, 10 * 200);
+        assert_eq!(log.len(), 15);
+        assert_eq!(log[0].date, chrono::NaiveDate::from_ymd(3013, 11, 17));
+        assert_eq!(log[0].message, "You paid 200 credits in crew salaries.");
+        assert_eq!(log[14].message, "You could not make all your payments.");
+    }
+
+    #[test]
+    fn can_register_daily_systems() {
+        let mut game = game();
+        game.register_daily_system("counter", |game| {
+            game.conditions
+                .apply(&crate::conditions::Condition::Increment(String::from(
+                    "days waited",
+                )));
+            vec![String::from("waited")]
+        });
+
+        let log = game.advance_days(3);
+        assert_eq!(game.conditions.get("days waited"), 3);
+        assert_eq!(
+            log.iter().filter(|entry| entry.message == "waited").count(),
+            3
+        );
+    }
+
+    #[test]
+    fn stops_on_the_last_date() {
+        let mut game = game();
+        game.current_date = chrono::NaiveDate::MAX.pred_opt().unwrap();
+        let log = game.advance_days(3);
+        assert_eq!(game.current_date, chrono::NaiveDate::MAX);
+        assert_eq!(log.len(), 1);
+    }
+}
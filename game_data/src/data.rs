@@ -2,7 +2,212 @@ use std::sync::Arc;
 
 use gdnative::{FromVariant, ToVariant};
 
-use helpers::max;
+use helpers::{max, min};
+
+/// number of game steps per second in ES, used to convert animation timings
+const ES_STEPS_PER_SECOND: f32 = 60.0;
+
+/// maximum distance of a system that can be reached with a jump drive
+pub const JUMP_DRIVE_RANGE: f32 = 100.0;
+/// fuel a ramscoop of strength 1 is assumed to collect while the ship crosses a system
+pub const RAMSCOOP_FUEL_PER_JUMP: f32 = 10.0;
+
+/// How the frames of a sprite are blended, depending on the separator in their file name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlendingMode {
+    /// normal alpha blending, `name-N.png`
+    Alpha,
+    /// additive blending, `name+N.png`
+    Additive,
+    /// half additive blending, `name~N.png`
+    HalfAdditive,
+    /// premultiplied alpha, `name=N.png`
+    PremultipliedAlpha,
+}
+
+impl BlendingMode {
+    /// all blending modes, in the order they should be looked for
+    pub const ALL: [BlendingMode; 4] = [
+        BlendingMode::Alpha,
+        BlendingMode::Additive,
+        BlendingMode::HalfAdditive,
+        BlendingMode::PremultipliedAlpha,
+    ];
+
+    /// separator between the sprite name and the frame number
+    pub fn separator(self) -> char {
+        match self {
+            BlendingMode::Alpha => '-',
+            BlendingMode::Additive => '+',
+            BlendingMode::HalfAdditive => '~',
+            BlendingMode::PremultipliedAlpha => '=',
+        }
+    }
+}
+
+/// A sprite, with it's animation settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    /// it's name, as a path relative to the images folder without extension
+    pub name: String,
+    /// number of frames displayed per second
+    pub frame_rate: f32,
+    /// number of frames to pause on the first frame between two loops
+    pub delay: u32,
+    /// should the animation start at a random frame
+    pub random_start_frame: bool,
+    /// should the animation stay on it's last frame instead of looping
+    pub no_repeat: bool,
+}
+
+impl Sprite {
+    /// default frame rate of ES animations
+    pub const DEFAULT_FRAME_RATE: f32 = 2.0;
+
+    /// create a sprite from ES data
+    pub fn from_es_sprite(sprite: &es_data_parser::Sprite) -> Self {
+        match sprite {
+            es_data_parser::Sprite::Simple(name) => Sprite {
+                name: String::from(*name),
+                frame_rate: Self::DEFAULT_FRAME_RATE,
+                delay: 0,
+                random_start_frame: false,
+                no_repeat: false,
+            },
+            es_data_parser::Sprite::Sprite {
+                name,
+                frame_time,
+                delay,
+                random_start_frame,
+                no_repeat,
+                frame_rate,
+            } => Sprite {
+                name: String::from(*name),
+                frame_rate: match (frame_rate, frame_time) {
+                    (Some(frame_rate), _) => *frame_rate,
+                    (None, Some(frame_time)) if *frame_time > 0 => {
+                        ES_STEPS_PER_SECOND / *frame_time as f32
+                    }
+                    _ => Self::DEFAULT_FRAME_RATE,
+                },
+                delay: delay.unwrap_or(0),
+                random_start_frame: *random_start_frame,
+                no_repeat: *no_repeat,
+            },
+        }
+    }
+
+    /// path of the sprite when it has a single frame
+    pub fn single_frame_path(&self) -> String {
+        format!("{}.png", self.name)
+    }
+
+    /// path of a frame of the sprite
+    pub fn frame_path(&self, blending: BlendingMode, frame: usize) -> String {
+        format!("{}{}{}.png", self.name, blending.separator(), frame)
+    }
+
+    /// paths of all the frames of the sprite
+    pub fn frame_paths(&self, blending: BlendingMode, frame_count: usize) -> Vec<String> {
+        (0..frame_count)
+            .map(|frame| self.frame_path(blending, frame))
+            .collect()
+    }
+
+    /// frame to display after some time in seconds, for a sprite with `frame_count` frames
+    pub fn frame_at(&self, elapsed: f32, frame_count: usize) -> usize {
+        if frame_count <= 1 {
+            return 0;
+        }
+        let frame = max!(elapsed * self.frame_rate, 0.0) as usize;
+        if self.no_repeat {
+            return frame.min(frame_count - 1);
+        }
+        let frame = frame % (frame_count + self.delay as usize);
+        if frame < frame_count {
+            frame
+        } else {
+            0
+        }
+    }
+}
+
+/// declare the attributes that add up when outfits are installed on a ship
+macro_rules! attributes {
+    ($($(#[$doc:meta])* $field:ident,)*) => {
+        /// Attributes of a ship or an outfit, that add up when outfits are installed on a ship.
+        /// Space and capacities used by an outfit are negative.
+        #[derive(Debug, Clone, Copy, Default, PartialEq)]
+        pub struct Attributes {
+            $($(#[$doc])* pub $field: f32,)*
+        }
+
+        impl Attributes {
+            /// add the attributes of a number of outfits
+            pub fn add(&mut self, other: &Attributes, count: f32) {
+                $(self.$field += other.$field * count;)*
+            }
+
+            /// keep only the positive attributes
+            pub fn positive(&self) -> Attributes {
+                Attributes {
+                    $($field: max!(self.$field, 0.0),)*
+                }
+            }
+        }
+    };
+}
+
+attributes! {
+    /// energy that can be stored
+    energy_capacity,
+    /// energy generated each second
+    energy_generation,
+    /// energy consumed each second
+    energy_consumption,
+    /// energy collected from stars each second
+    solar_collection,
+    /// heat generated each second
+    heat_generation,
+    /// heat removed each second by active cooling
+    cooling,
+    /// share of the heat dissipated each second
+    heat_dissipation,
+    /// shields
+    shields,
+    /// shields regenerated each second
+    shield_generation,
+    /// energy used to regenerate shields
+    shield_energy,
+    /// hull
+    hull,
+    /// hull repaired each second
+    hull_repair_rate,
+    /// energy used to repair the hull
+    hull_energy,
+    /// space for cargo
+    cargo_space,
+    /// space for outfits
+    outfit_space,
+    /// space for weapons
+    weapon_capacity,
+    /// space for engines
+    engine_capacity,
+    /// ports for guns
+    gun_ports,
+    /// mounts for turrets
+    turret_mounts,
+    /// fuel that can be stored
+    fuel_capacity,
+    /// fuel collected from stars
+    ramscoop,
+    /// bunks for crew and passengers
+    bunks,
+    /// crew needed
+    required_crew,
+    /// credits paid each day
+    maintenance_costs,
+}
 
 /// An outfit
 #[derive(Debug, Clone)]
@@ -15,6 +220,55 @@ pub struct Outfit {
     pub category: OutfitCategory,
     /// does this outfit has engine properties
     pub engine: Vec<OutfitEngine>,
+    /// sprite of the flare when the engine is used
+    pub flare_sprite: Option<Sprite>,
+    /// does this outfit let the ship jump between systems
+    pub drive: Vec<OutfitDrive>,
+    /// attributes it adds to the ship
+    pub attributes: Attributes,
+    /// ammunition it can hold, or that it is when negative, by kind
+    pub ammo_capacity: Vec<(String, f32)>,
+    /// it's price, in credits
+    pub cost: u32,
+    /// fine for carrying it, before the multiplier of the government, 0 when legal
+    pub illegal: f32,
+    /// number of jumps around the current system revealed when bought, 0 when not a map
+    pub map: u32,
+    /// licenses needed to buy it
+    pub licenses: Vec<String>,
+}
+
+/// A drive part of an outfit, used to jump between systems
+#[derive(Debug, Clone, Copy)]
+pub struct OutfitDrive {
+    /// type of drive
+    pub ty: DriveType,
+    /// fuel used for each jump
+    pub fuel: f32,
+    /// speed of the jump
+    pub speed: Option<f32>,
+}
+
+/// Type of a drive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriveType {
+    /// jump to a linked system
+    Hyperdrive,
+    /// jump to a linked system, faster than a hyperdrive
+    ScramDrive,
+    /// jump to any system in range
+    JumpDrive,
+}
+
+impl DriveType {
+    /// fuel used for a jump when the outfit doesn't say otherwise
+    pub fn default_fuel(self) -> f32 {
+        match self {
+            DriveType::Hyperdrive => 100.0,
+            DriveType::ScramDrive => 150.0,
+            DriveType::JumpDrive => 200.0,
+        }
+    }
 }
 
 /// An engine part of an outfit
@@ -66,30 +320,113 @@ pub enum OutfitCategory {
     Unspecified,
 }
 
-/// A ship
+/// A ship model, as described in the data files
 #[derive(Debug, Clone)]
-pub struct Ship {
+pub struct ShipModel {
     /// it's name
     pub name: String,
     /// it's sprite
-    pub sprite: String,
-    /// it's outfits
+    pub sprite: Sprite,
+    /// outfits it's sold with
     pub outfits: Vec<(Outfit, u32)>,
     /// it's drag
     pub drag: f32,
     /// it's mass
-    pub base_mass: u32,
+    pub base_mass: f32,
+    /// it's attributes, without outfits
+    pub attributes: Attributes,
+    /// price of the ship without it's outfits, in credits
+    pub cost: u32,
+    /// licenses needed to buy it
+    pub licenses: Vec<String>,
+}
+
+impl ShipModel {
+    /// get the price of the ship with the outfits it's sold with, in credits
+    pub fn get_cost(&self) -> i64 {
+        i64::from(self.cost) + outfits_cost(&self.outfits)
+    }
 }
 
-impl Ship {
+fn outfits_cost(outfits: &[(Outfit, u32)]) -> i64 {
+    outfits
+        .iter()
+        .map(|(outfit, count)| i64::from(outfit.cost) * i64::from(*count))
+        .sum()
+}
+
+/// A ship built from a model, with it's own name, state and outfits
+#[derive(Debug, Clone)]
+pub struct ShipInstance {
+    /// the model it was built from
+    pub model: Arc<ShipModel>,
+    /// name given to the ship
+    pub name: String,
+    /// it's installed outfits
+    pub outfits: Vec<(Outfit, u32)>,
+    /// what it carries in it's hold
+    pub cargo: crate::cargo::Cargo,
+    /// hull left
+    pub hull: f32,
+    /// shields left
+    pub shields: f32,
+    /// energy left
+    pub energy: f32,
+    /// heat accumulated
+    pub heat: f32,
+    /// fuel left
+    pub fuel: f32,
+    /// name of the planet it's parked on, instead of following the flagship
+    pub parked_on: Option<String>,
+}
+
+impl ShipInstance {
+    /// Build a ship from a model, with the outfits it's sold with, fully repaired and refueled
+    pub fn new(name: &str, model: Arc<ShipModel>) -> Self {
+        let mut ship = ShipInstance {
+            name: String::from(name),
+            outfits: model.outfits.clone(),
+            model,
+            cargo: Default::default(),
+            hull: 0.0,
+            shields: 0.0,
+            energy: 0.0,
+            heat: 0.0,
+            fuel: 0.0,
+            parked_on: None,
+        };
+        ship.restore();
+        ship
+    }
+
+    /// repair, recharge, cool down and refuel the ship
+    pub fn restore(&mut self) {
+        let stats = self.stats();
+        self.hull = stats.hull;
+        self.shields = stats.shields;
+        self.energy = stats.energy_capacity;
+        self.heat = 0.0;
+        self.fuel = stats.fuel_capacity;
+    }
+
+    /// is the ship parked on a planet
+    pub fn is_parked(&self) -> bool {
+        self.parked_on.is_some()
+    }
+
+    /// get the price of the ship with it's installed outfits, in credits
+    pub fn get_cost(&self) -> i64 {
+        i64::from(self.model.cost) + outfits_cost(&self.outfits)
+    }
+
     /// get drag of the ship
     pub fn get_drag(&self) -> f32 {
-        self.drag
+        self.model.drag
     }
 
-    /// get mass of the ship plus it's outfits
+    /// get mass of the ship plus it's outfits and cargo
     pub fn get_mass(&self) -> f32 {
-        let mut total_mass: f32 = self.base_mass as f32;
+        let mut total_mass: f32 = self.model.base_mass + self.cargo.mass();
         total_mass += self
             .outfits
             .iter()
@@ -100,31 +437,98 @@ impl Ship {
 
     /// get the sum of the thrust of all engines
     pub fn get_forward_thrust(&self) -> f32 {
+        self.get_engines(EngineType::Thrust)
+            .map(|(engine, count)| engine.power * count)
+            .sum()
+    }
+
+    /// get the sum of the reverse thrust of all engines
+    pub fn get_reverse_thrust(&self) -> f32 {
+        self.get_engines(EngineType::ReverseThrust)
+            .map(|(engine, count)| engine.power * count)
+            .sum()
+    }
+
+    /// get the engines of a type, with how many of them are installed
+    pub fn get_engines(&self, ty: EngineType) -> impl Iterator<Item = (&OutfitEngine, f32)> {
         self.outfits
             .iter()
-            .flat_map(|outfit| outfit.0.engine.iter().map(move |engine| (engine, outfit.1)))
-            .filter_map(|engine| {
-                if engine.0.ty == EngineType::Thrust {
-                    Some(engine.0.power * (engine.1 as f32))
-                } else {
-                    None
-                }
+            .flat_map(|outfit| {
+                outfit
+                    .0
+                    .engine
+                    .iter()
+                    .map(move |engine| (engine, outfit.1 as f32))
             })
-            .sum()
+            .filter(move |(engine, _)| engine.ty == ty)
     }
 
-    /// get the sum of the turn of all engines
-    pub fn get_turn(&self) -> f32 {
+    /// get attributes of the ship plus it's outfits
+    pub fn get_attributes(&self) -> Attributes {
+        let mut attributes = self.model.attributes;
+        for (outfit, count) in &self.outfits {
+            attributes.add(&outfit.attributes, *count as f32);
+        }
+        attributes
+    }
+
+    /// get fuel capacity of the ship plus it's outfits
+    pub fn get_fuel_capacity(&self) -> f32 {
+        self.get_attributes().fuel_capacity
+    }
+
+    /// get the sum of the ramscoop of all outfits
+    pub fn get_ramscoop(&self) -> f32 {
+        self.get_attributes().ramscoop
+    }
+
+    /// get the fuel collected by the ramscoops while crossing a system
+    pub fn get_fuel_regeneration_per_jump(&self) -> f32 {
+        RAMSCOOP_FUEL_PER_JUMP * self.get_ramscoop().sqrt()
+    }
+
+    /// get the fuel used by the most efficient drive of the given types, if the ship has one
+    fn get_drive_fuel(&self, types: &[DriveType]) -> Option<f32> {
         self.outfits
             .iter()
-            .flat_map(|outfit| outfit.0.engine.iter().map(move |engine| (engine, outfit.1)))
-            .filter_map(|engine| {
-                if engine.0.ty == EngineType::Turn {
-                    Some(engine.0.power * (engine.1 as f32))
-                } else {
-                    None
-                }
+            .flat_map(|outfit| outfit.0.drive.iter())
+            .filter(|drive| types.contains(&drive.ty))
+            .map(|drive| drive.fuel)
+            .fold(None, |best: Option<f32>, fuel| {
+                Some(best.map_or(fuel, |best| min!(best, fuel)))
             })
+    }
+
+    /// get the fuel used to jump to a linked system, if the ship has a hyperdrive
+    pub fn get_hyperdrive_fuel(&self) -> Option<f32> {
+        self.get_drive_fuel(&[DriveType::Hyperdrive, DriveType::ScramDrive])
+    }
+
+    /// get the fuel used to jump to a system in range, if the ship has a jump drive
+    pub fn get_jump_drive_fuel(&self) -> Option<f32> {
+        self.get_drive_fuel(&[DriveType::JumpDrive])
+    }
+
+    /// get the fuel needed to jump from a system to another, if the ship can make that jump
+    pub fn get_jump_fuel(&self, from: &System, to: &System) -> Option<f32> {
+        if from.name == to.name {
+            return None;
+        }
+        if from.is_linked_to(to) {
+            if let Some(fuel) = self.get_hyperdrive_fuel() {
+                return Some(fuel);
+            }
+        }
+        if (to.position - from.position).length() <= JUMP_DRIVE_RANGE {
+            return self.get_jump_drive_fuel();
+        }
+        None
+    }
+
+    /// get the sum of the turn of all engines
+    pub fn get_turn(&self) -> f32 {
+        self.get_engines(EngineType::Turn)
+            .map(|(engine, count)| engine.power * count)
             .sum()
     }
 }
@@ -132,25 +536,198 @@ impl Ship {
 /// A player
 #[derive(Debug)]
 pub struct Player {
-    /// it's ship
-    pub ship: Arc<Ship>,
+    /// it's ships
+    pub fleet: Vec<ShipInstance>,
+    /// index in the fleet of the ship it's flying
+    pub flagship: usize,
     /// it's current system
     pub current_system: Arc<System>,
+    /// it's account
+    pub account: crate::account::Account,
+    /// the planet it's landed on
+    pub current_planet: Option<Arc<Planet>>,
+    /// when it bought it's ships and outfits, to know how much they can be sold for
+    pub depreciation: crate::shop::Depreciation,
+    /// it's reputation with each government
+    pub reputations: crate::politics::Reputations,
+    /// planets of the current system it paid a bribe to land on
+    pub bribed_planets: Vec<String>,
+    /// systems and planets it visited or knows about
+    pub exploration: crate::exploration::Exploration,
+    /// missions it accepted, or that are offered to it
+    pub missions: crate::missions::Missions,
+}
+
+/// A government, that systems, planets and ships belong to
+#[derive(Debug, Clone)]
+pub struct Government {
+    /// it's name
+    pub name: String,
+    /// name shown to the player
+    pub display_name: String,
+    /// color of it's systems on the map, as red, green and blue between 0 and 1
+    pub color: Option<(f32, f32, f32)>,
+    /// reputation of the player with it when a game starts
+    pub initial_reputation: f32,
+    /// attitude toward other governments, negative when hostile
+    pub attitudes: Vec<(String, f32)>,
+    /// reputation lost for each event, negative when reputation is gained
+    pub penalties: Vec<(crate::politics::ShipEvent, f32)>,
+    /// share of the player credits asked as a bribe
+    pub bribe: f32,
+    /// multiplier of the fines it gives
+    pub fine: f32,
+}
+
+impl Government {
+    /// get the attitude toward another government, fully positive toward itself
+    pub fn attitude_toward(&self, other: &str) -> f32 {
+        if other == self.name {
+            return 1.0;
+        }
+        self.attitudes
+            .iter()
+            .find(|(name, _)| name == other)
+            .map(|(_, attitude)| *attitude)
+            .unwrap_or(0.0)
+    }
+
+    /// get the reputation lost for an event
+    pub fn penalty_for(&self, event: crate::politics::ShipEvent) -> f32 {
+        self.penalties
+            .iter()
+            .find(|(found, _)| *found == event)
+            .map(|(_, penalty)| *penalty)
+            .unwrap_or_else(|| event.default_penalty())
+    }
+}
+
+/// A planet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Planet {
+    /// it's name
+    pub name: String,
+    /// name of it's government, if different from the one of it's system
+    pub government: Option<String>,
+    /// it's attributes, used by missions and events to find planets
+    pub attributes: Vec<String>,
+    /// names of the ships sold in it's shipyard
+    pub shipyard: Vec<String>,
+    /// names of the outfits sold in it's outfitter
+    pub outfitter: Vec<String>,
+    /// it's description, shown when landed
+    pub description: String,
+    /// description of it's spaceport
+    pub spaceport: String,
+    /// image shown when landed
+    pub landscape: Option<String>,
+    /// share of the value of the fleet asked as a bribe to land, 0 when it can't be bribed
+    pub bribe: f32,
+    /// probability that the fleet is scanned for illegal outfits when landing
+    pub security: f32,
+    /// reputation needed with it's government to land, if any
+    pub required_reputation: Option<f32>,
+    /// tribute it can pay to the player, if any
+    pub tribute: Option<Tribute>,
+}
+
+/// Credits a planet pays each day to a player that dominates it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tribute {
+    /// credits paid each day
+    pub value: i64,
+    /// combat rating needed before the planet considers paying
+    pub threshold: i32,
+    /// name of the fleet defending the planet
+    pub fleet: String,
+    /// number of defending fleets
+    pub fleet_count: u32,
+}
+
+impl Planet {
+    /// share of the value of the fleet asked as a bribe when not set in the data files
+    pub const DEFAULT_BRIBE: f32 = 0.01;
+    /// probability of a scan when landing when not set in the data files
+    pub const DEFAULT_SECURITY: f32 = 0.25;
+
+    /// does it have an attribute
+    pub fn has_attribute(&self, attribute: &str) -> bool {
+        self.attributes
+            .iter()
+            .any(|candidate| candidate == attribute)
+    }
+
+    /// does it have a spaceport
+    pub fn has_spaceport(&self) -> bool {
+        !self.spaceport.is_empty()
+    }
+
+    /// does it have a shipyard selling ships
+    pub fn has_shipyard(&self) -> bool {
+        !self.shipyard.is_empty()
+    }
+
+    /// does it have an outfitter selling outfits
+    pub fn has_outfitter(&self) -> bool {
+        !self.outfitter.is_empty()
+    }
+
+    /// is it inhabited, which is when it has a spaceport, a required reputation or defense
+    /// fleets, and is not marked as uninhabited
+    pub fn is_inhabited(&self) -> bool {
+        (self.has_spaceport()
+            || self
+                .required_reputation
+                .map_or(false, |reputation| reputation != 0.0)
+            || self
+                .tribute
+                .as_ref()
+                .map_or(false, |tribute| tribute.fleet_count > 0))
+            && !self.has_attribute("uninhabited")
+    }
 }
 
 /// A stellar object
 #[derive(Debug, ToVariant, FromVariant, Clone)]
 pub struct Object {
+    /// name of the planet it is, if it's one
+    pub planet: Option<String>,
+    /// is it a planet defined in the data files, that can be landed on
+    pub landable: bool,
     /// it's sprite
     pub sprite: Option<String>,
     /// it's distance to it's parent
     pub distance: f32,
     /// it's period
     pub period: f32,
+    /// angle of it's orbit at the beginning of time, in degrees
+    pub offset: f32,
     /// it's subobjects
     pub objects: Vec<Object>,
 }
 
+/// A galaxy, drawn behind the systems on the universe map
+#[derive(Debug, ToVariant, FromVariant, Clone)]
+pub struct Galaxy {
+    /// it's name
+    pub name: String,
+    /// position of it's center in the universe map
+    pub position: euclid::Vector2D<f32, euclid::UnknownUnit>,
+    /// it's sprite
+    pub sprite: Option<String>,
+}
+
+/// A commodity traded in a system
+#[derive(Debug, ToVariant, FromVariant, Clone)]
+pub struct Trade {
+    /// name of the commodity
+    pub commodity: String,
+    /// price in credits per ton given by the data files
+    pub base_price: i64,
+    /// price in credits per ton today, drifting around the base price
+    pub price: i64,
+}
+
 /// A system
 #[derive(Debug, ToVariant, FromVariant, Clone)]
 pub struct System {
@@ -160,4 +737,163 @@ pub struct System {
     pub objects: Vec<Object>,
     /// position of the system in the universe map
     pub position: euclid::Vector2D<f32, euclid::UnknownUnit>,
+    /// names of the systems reachable with a hyperdrive
+    pub links: Vec<String>,
+    /// commodities traded here
+    pub trades: Vec<Trade>,
+    /// name of it's government
+    pub government: String,
+}
+
+impl System {
+    /// names of the planets in it, including the ones orbiting other objects
+    pub fn planets(&self) -> Vec<&str> {
+        fn planets_of<'a>(objects: &'a [Object], planets: &mut Vec<&'a str>) {
+            for object in objects {
+                if let Some(planet) = &object.planet {
+                    planets.push(planet);
+                }
+                planets_of(&object.objects, planets);
+            }
+        }
+        let mut planets = vec![];
+        planets_of(&self.objects, &mut planets);
+        planets
+    }
+
+    /// check if a system can be reached with a hyperdrive
+    pub fn is_linked_to(&self, other: &System) -> bool {
+        self.links.iter().any(|link| link == &other.name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn animated_sprite() -> Sprite {
+        Sprite::from_es_sprite(&es_data_parser::Sprite::Sprite {
+            name: "effect/blink",
+            frame_time: None,
+            delay: Some(2),
+            random_start_frame: false,
+            no_repeat: false,
+            frame_rate: Some(10.0),
+        })
+    }
+
+    #[test]
+    fn can_expand_sprite_frame_paths() {
+        let sprite = animated_sprite();
+        assert_eq!(sprite.single_frame_path(), "effect/blink.png");
+        assert_eq!(
+            sprite.frame_paths(BlendingMode::Additive, 3),
+            vec![
+                "effect/blink+0.png",
+                "effect/blink+1.png",
+                "effect/blink+2.png"
+            ]
+        );
+        assert_eq!(
+            sprite.frame_path(BlendingMode::PremultipliedAlpha, 4),
+            "effect/blink=4.png"
+        );
+        assert_eq!(
+            sprite.frame_path(BlendingMode::Alpha, 0),
+            "effect/blink-0.png"
+        );
+    }
+
+    #[test]
+    fn can_convert_frame_time_to_frame_rate() {
+        let sprite = Sprite::from_es_sprite(&es_data_parser::Sprite::Sprite {
+            name: "ship/slow",
+            frame_time: Some(30),
+            delay: None,
+            random_start_frame: true,
+            no_repeat: false,
+            frame_rate: None,
+        });
+        assert_eq!(sprite.frame_rate, 2.0);
+        assert!(sprite.random_start_frame);
+
+        let sprite = Sprite::from_es_sprite(&es_data_parser::Sprite::Simple("ship/fixed"));
+        assert_eq!(sprite.frame_rate, Sprite::DEFAULT_FRAME_RATE);
+    }
+
+    #[test]
+    fn can_find_frame_to_display() {
+        let sprite = animated_sprite();
+        assert_eq!(sprite.frame_at(0.0, 3), 0);
+        assert_eq!(sprite.frame_at(0.25, 3), 2);
+        // paused on first frame during the delay
+        assert_eq!(sprite.frame_at(0.35, 3), 0);
+        assert_eq!(sprite.frame_at(0.45, 3), 0);
+        assert_eq!(sprite.frame_at(0.55, 3), 0);
+        assert_eq!(sprite.frame_at(0.65, 3), 1);
+
+        let sprite = Sprite {
+            no_repeat: true,
+            ..animated_sprite()
+        };
+        assert_eq!(sprite.frame_at(10.0, 3), 2);
+        assert_eq!(sprite.frame_at(10.0, 1), 0);
+    }
+
+    #[test]
+    fn can_build_ships_with_their_own_state_from_a_model() {
+        let fuel_pod = Outfit {
+            name: String::from("Fuel Pod"),
+            mass: 5.0,
+            category: OutfitCategory::Systems,
+            engine: vec![],
+            flare_sprite: None,
+            drive: vec![],
+            attributes: Attributes {
+                fuel_capacity: 100.0,
+                outfit_space: -5.0,
+                ..Default::default()
+            },
+            ammo_capacity: vec![],
+            cost: 1000,
+            illegal: 0.0,
+            map: 0,
+            licenses: vec![],
+        };
+        let model = Arc::new(ShipModel {
+            name: String::from("Shuttle"),
+            sprite: Sprite::from_es_sprite(&es_data_parser::Sprite::Simple("ship/shuttle")),
+            outfits: vec![(fuel_pod.clone(), 1)],
+            drag: 1.0,
+            base_mass: 50.0,
+            attributes: Attributes {
+                hull: 600.0,
+                fuel_capacity: 300.0,
+                outfit_space: 20.0,
+                ..Default::default()
+            },
+            cost: 10000,
+            licenses: vec![],
+        });
+
+        let mut first = ShipInstance::new("First", model.clone());
+        let second = ShipInstance::new("Second", model.clone());
+        assert_eq!(first.fuel, 400.0);
+        assert_eq!(first.hull, 600.0);
+        assert_eq!(first.get_cost(), 11000);
+
+        first.install(&fuel_pod, 2).unwrap();
+        first.fuel = 50.0;
+        first.cargo.add_commodity("Food", 10);
+        assert_eq!(first.get_fuel_capacity(), 600.0);
+        assert_eq!(first.get_mass(), 75.0);
+        assert_eq!(second.get_fuel_capacity(), 400.0);
+        assert_eq!(second.fuel, 400.0);
+        assert!(second.cargo.is_empty());
+        assert_eq!(model.outfits.len(), 1);
+        assert_eq!(model.get_cost(), 11000);
+
+        first.restore();
+        assert_eq!(first.fuel, 600.0);
+    }
 }
@@ -0,0 +1,410 @@
+use std::fmt;
+
+/// Error when creating a new game from the loaded data
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameCreationError {
+    /// no ship was found in the data files
+    NoShips,
+    /// no system was found in the data files
+    NoSystems,
+    /// the start system was not found in the data files
+    UnknownStartSystem(String),
+    /// the start block gives no ship to the player
+    NoStartShip,
+    /// a start ship was not found in the data files
+    UnknownStartShip(String),
+    /// the start date is not a valid date
+    InvalidStartDate {
+        /// year of the start date
+        year: i32,
+        /// month of the start date
+        month: u32,
+        /// day of the start date
+        day: u32,
+    },
+}
+
+impl fmt::Display for GameCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameCreationError::NoShips => write!(f, "no ship found in data files"),
+            GameCreationError::NoSystems => write!(f, "no system found in data files"),
+            GameCreationError::UnknownStartSystem(system) => {
+                write!(f, "start system '{}' not found", system)
+            }
+            GameCreationError::NoStartShip => write!(f, "no ship given in start block"),
+            GameCreationError::UnknownStartShip(ship) => {
+                write!(f, "start ship '{}' not found", ship)
+            }
+            GameCreationError::InvalidStartDate { year, month, day } => {
+                write!(f, "start date {}/{}/{} is not valid", day, month, year)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameCreationError {}
+
+/// Error when saving a game, or loading a saved game
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveError {
+    /// the save file could not be read or written
+    Io(String),
+    /// the save file is not in a valid format
+    Format(String),
+    /// the save file was written with an unsupported version of the format
+    UnsupportedVersion(u32),
+    /// the save file has no ship for the player
+    NoShip,
+    /// the saved ship was not found in the data files
+    UnknownShip(String),
+    /// a saved outfit was not found in the data files
+    UnknownOutfit(String),
+    /// the saved system was not found in the data files
+    UnknownSystem(String),
+    /// the saved planet was not found in the data files
+    UnknownPlanet(String),
+    /// the saved date is not a valid date
+    InvalidDate {
+        /// year of the saved date
+        year: i32,
+        /// month of the saved date
+        month: u32,
+        /// day of the saved date
+        day: u32,
+    },
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "could not access save file: {}", error),
+            SaveError::Format(error) => write!(f, "invalid save file: {}", error),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "unsupported save file version {}", version)
+            }
+            SaveError::NoShip => write!(f, "no ship in save file"),
+            SaveError::UnknownShip(ship) => write!(f, "saved ship '{}' not found", ship),
+            SaveError::UnknownOutfit(outfit) => write!(f, "saved outfit '{}' not found", outfit),
+            SaveError::UnknownSystem(system) => write!(f, "saved system '{}' not found", system),
+            SaveError::UnknownPlanet(planet) => write!(f, "saved planet '{}' not found", planet),
+            SaveError::InvalidDate { year, month, day } => {
+                write!(f, "saved date {}/{}/{} is not valid", day, month, year)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Error when jumping to another system
+#[derive(Debug, Clone, PartialEq)]
+pub enum JumpError {
+    /// the system was not found in the data files
+    UnknownSystem(String),
+    /// the ship has no drive that can reach the system
+    Unreachable(String),
+    /// the ship doesn't have enough fuel for the jump
+    NotEnoughFuel {
+        /// fuel needed for the jump
+        needed: f32,
+        /// fuel left in the ship
+        available: f32,
+    },
+}
+
+impl fmt::Display for JumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JumpError::UnknownSystem(system) => write!(f, "system '{}' not found", system),
+            JumpError::Unreachable(system) => write!(f, "system '{}' can't be reached", system),
+            JumpError::NotEnoughFuel { needed, available } => write!(
+                f,
+                "not enough fuel: {} needed, {} available",
+                needed, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JumpError {}
+
+/// Error when landing on a planet
+#[derive(Debug, Clone, PartialEq)]
+pub enum LandingError {
+    /// the planet was not found in the data files
+    UnknownPlanet(String),
+    /// the planet is not in the current system
+    NotInSystem(String),
+    /// the reputation of the player with the government of the planet is too low
+    NotEnoughReputation {
+        /// reputation needed to land
+        needed: f32,
+        /// reputation of the player
+        available: f32,
+    },
+    /// the government of the planet is hostile to the player
+    Hostile(String),
+    /// the planet can't be bribed
+    NoBribe(String),
+    /// the player doesn't have enough credits for the bribe
+    NotEnoughCredits {
+        /// credits needed
+        needed: i64,
+        /// credits of the player
+        available: i64,
+    },
+}
+
+impl fmt::Display for LandingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LandingError::UnknownPlanet(planet) => write!(f, "planet '{}' not found", planet),
+            LandingError::NotInSystem(planet) => {
+                write!(f, "planet '{}' is not in this system", planet)
+            }
+            LandingError::NotEnoughReputation { needed, available } => write!(
+                f,
+                "not enough reputation: {} needed, {} available",
+                needed, available
+            ),
+            LandingError::Hostile(government) => {
+                write!(f, "{} refuses to let you land", government)
+            }
+            LandingError::NoBribe(planet) => write!(f, "planet '{}' can't be bribed", planet),
+            LandingError::NotEnoughCredits { needed, available } => write!(
+                f,
+                "not enough credits: {} needed, {} available",
+                needed, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LandingError {}
+
+/// A space or capacity of a ship, used by outfits
+#[derive(Debug, Clone, PartialEq)]
+pub enum Space {
+    /// space for cargo
+    CargoSpace,
+    /// space for outfits
+    OutfitSpace,
+    /// space for weapons
+    WeaponCapacity,
+    /// space for engines
+    EngineCapacity,
+    /// ports for guns
+    GunPorts,
+    /// mounts for turrets
+    TurretMounts,
+    /// capacity for a kind of ammunition
+    Ammo(String),
+}
+
+impl fmt::Display for Space {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Space::CargoSpace => write!(f, "cargo space"),
+            Space::OutfitSpace => write!(f, "outfit space"),
+            Space::WeaponCapacity => write!(f, "weapon capacity"),
+            Space::EngineCapacity => write!(f, "engine capacity"),
+            Space::GunPorts => write!(f, "gun ports"),
+            Space::TurretMounts => write!(f, "turret mounts"),
+            Space::Ammo(kind) => write!(f, "{} capacity", kind),
+        }
+    }
+}
+
+/// Error when installing or uninstalling outfits on a ship
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallError {
+    /// the ship doesn't have enough of a space
+    NotEnoughSpace {
+        /// the missing space
+        space: Space,
+        /// space needed
+        needed: f32,
+        /// space left in the ship
+        available: f32,
+    },
+    /// not enough of the outfit is installed to remove it
+    NotInstalled {
+        /// name of the outfit
+        outfit: String,
+        /// number of this outfit installed
+        installed: u32,
+    },
+}
+
+impl fmt::Display for InstallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallError::NotEnoughSpace {
+                space,
+                needed,
+                available,
+            } => write!(
+                f,
+                "not enough {}: {} needed, {} available",
+                space, needed, available
+            ),
+            InstallError::NotInstalled { outfit, installed } => {
+                write!(f, "only {} '{}' installed", installed, outfit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstallError {}
+
+/// Error when buying or selling ships, outfits and commodities
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeError {
+    /// the player is not landed on a planet
+    NotLanded,
+    /// the ship or outfit was not found in the data files
+    Unknown(String),
+    /// the ship or outfit is not sold on this planet
+    NotSold(String),
+    /// the player doesn't have enough credits
+    NotEnoughCredits {
+        /// credits needed
+        needed: i64,
+        /// credits of the player
+        available: i64,
+    },
+    /// the player doesn't have a license needed to buy a ship
+    MissingLicense(String),
+    /// the outfit, like a map, can only be bought one at a time
+    OnlyOne(String),
+    /// the outfit can't be installed or uninstalled
+    Install(InstallError),
+    /// the ship can't be sold
+    Fleet(FleetError),
+    /// the fleet doesn't have enough cargo space
+    NotEnoughCargoSpace {
+        /// tons needed
+        needed: u32,
+        /// tons of cargo space left
+        available: u32,
+    },
+    /// the fleet doesn't carry enough of a commodity
+    NotEnoughCargo {
+        /// the commodity
+        item: String,
+        /// tons carried
+        available: u32,
+    },
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TradeError::NotLanded => write!(f, "not landed on a planet"),
+            TradeError::Unknown(item) => write!(f, "'{}' not found", item),
+            TradeError::NotSold(item) => write!(f, "'{}' is not sold here", item),
+            TradeError::NotEnoughCredits { needed, available } => write!(
+                f,
+                "not enough credits: {} needed, {} available",
+                needed, available
+            ),
+            TradeError::MissingLicense(license) => write!(f, "{} license needed", license),
+            TradeError::OnlyOne(item) => write!(f, "'{}' can only be bought one at a time", item),
+            TradeError::Install(error) => write!(f, "{}", error),
+            TradeError::Fleet(error) => write!(f, "{}", error),
+            TradeError::NotEnoughCargoSpace { needed, available } => write!(
+                f,
+                "not enough cargo space: {} tons needed, {} available",
+                needed, available
+            ),
+            TradeError::NotEnoughCargo { item, available } => {
+                write!(f, "only {} tons of '{}' in cargo", available, item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TradeError {}
+
+impl From<InstallError> for TradeError {
+    fn from(error: InstallError) -> Self {
+        TradeError::Install(error)
+    }
+}
+
+impl From<FleetError> for TradeError {
+    fn from(error: FleetError) -> Self {
+        TradeError::Fleet(error)
+    }
+}
+
+/// Error when managing the ships of the player
+#[derive(Debug, Clone, PartialEq)]
+pub enum FleetError {
+    /// the player has no ship at this index
+    NoSuchShip(usize),
+    /// the flagship can't be parked or removed
+    IsFlagship,
+    /// the player is not landed on a planet
+    NotLanded,
+    /// the ship is parked on another planet
+    ParkedElsewhere(String),
+}
+
+impl fmt::Display for FleetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FleetError::NoSuchShip(index) => write!(f, "no ship {} in the fleet", index),
+            FleetError::IsFlagship => write!(f, "the flagship must stay with the player"),
+            FleetError::NotLanded => write!(f, "not landed on a planet"),
+            FleetError::ParkedElsewhere(planet) => write!(f, "the ship is parked on {}", planet),
+        }
+    }
+}
+
+impl std::error::Error for FleetError {}
+
+/// Error when accepting, declining or aborting missions
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissionError {
+    /// the mission is not offered to the player
+    NotOffered(String),
+    /// the mission was not accepted by the player
+    NotActive(String),
+    /// the fleet doesn't have enough cargo space for the mission cargo
+    NotEnoughCargoSpace {
+        /// tons needed
+        needed: u32,
+        /// tons of cargo space left
+        available: u32,
+    },
+    /// the fleet doesn't have enough bunks for the mission passengers
+    NotEnoughBunks {
+        /// bunks needed
+        needed: u32,
+        /// bunks left
+        available: u32,
+    },
+}
+
+impl fmt::Display for MissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissionError::NotOffered(mission) => write!(f, "mission '{}' is not offered", mission),
+            MissionError::NotActive(mission) => write!(f, "mission '{}' is not active", mission),
+            MissionError::NotEnoughCargoSpace { needed, available } => write!(
+                f,
+                "not enough cargo space: {} tons needed, {} available",
+                needed, available
+            ),
+            MissionError::NotEnoughBunks { needed, available } => write!(
+                f,
+                "not enough bunks: {} needed, {} available",
+                needed, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MissionError {}
@@ -0,0 +1,227 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::Game;
+
+/// Systems and planets the player visited, or learned about from maps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Exploration {
+    visited_systems: Vec<String>,
+    visited_planets: Vec<String>,
+    revealed_systems: Vec<String>,
+}
+
+fn add_once(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|known| known == name) {
+        names.push(String::from(name));
+    }
+}
+
+impl Exploration {
+    /// remember that a system was visited
+    pub fn visit_system(&mut self, system: &str) {
+        add_once(&mut self.visited_systems, system);
+    }
+
+    /// remember that a planet was landed on
+    pub fn visit_planet(&mut self, planet: &str) {
+        add_once(&mut self.visited_planets, planet);
+    }
+
+    /// remember a system seen on a map, without visiting it
+    pub fn reveal_system(&mut self, system: &str) {
+        add_once(&mut self.revealed_systems, system);
+    }
+
+    /// was a system visited
+    pub fn has_visited_system(&self, system: &str) -> bool {
+        self.visited_systems.iter().any(|visited| visited == system)
+    }
+
+    /// was a planet landed on
+    pub fn has_visited_planet(&self, planet: &str) -> bool {
+        self.visited_planets.iter().any(|visited| visited == planet)
+    }
+
+    /// was a system seen on a map
+    pub fn is_revealed(&self, system: &str) -> bool {
+        self.revealed_systems
+            .iter()
+            .any(|revealed| revealed == system)
+    }
+
+    /// names of the visited systems
+    pub fn visited_systems(&self) -> impl Iterator<Item = &str> {
+        self.visited_systems.iter().map(String::as_str)
+    }
+
+    /// names of the visited planets
+    pub fn visited_planets(&self) -> impl Iterator<Item = &str> {
+        self.visited_planets.iter().map(String::as_str)
+    }
+}
+
+impl Game {
+    /// has the player been in a system
+    pub fn is_visited_system(&self, system: &str) -> bool {
+        self.player.exploration.has_visited_system(system)
+    }
+
+    /// does the player know about a system, by having visited it or one of it's neighbours, or
+    /// from a map
+    pub fn is_known_system(&self, system: &str) -> bool {
+        let exploration = &self.player.exploration;
+        exploration.has_visited_system(system)
+            || exploration.is_revealed(system)
+            || self.systems.iter().any(|other| {
+                exploration.has_visited_system(&other.name)
+                    && other.links.iter().any(|link| link == system)
+            })
+    }
+
+    /// Reveal the systems within a number of jumps of the current system, as a map does
+    pub fn reveal_map(&mut self, jumps: u32) {
+        let mut to_explore = VecDeque::new();
+        to_explore.push_back((self.player.current_system.clone(), 0));
+        let mut seen = vec![self.player.current_system.name.clone()];
+        while let Some((system, distance)) = to_explore.pop_front() {
+            self.player.exploration.reveal_system(&system.name);
+            if distance == jumps {
+                continue;
+            }
+            for neighbour in self
+                .systems
+                .iter()
+                .filter(|other| system.is_linked_to(other))
+            {
+                if !seen.contains(&neighbour.name) {
+                    seen.push(neighbour.name.clone());
+                    to_explore.push_back((neighbour.clone(), distance + 1));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::save::SavedGame;
+    use crate::test_data;
+    use crate::{ESGameLoader, Game, TradeError};
+
+    const HOME: &str = "\tlink Neighbour\n\tobject \"Home\"\n\t\tperiod 10\n";
+
+    const DATA: &str = r#"outfit "Local Map"
+	category "Special"
+	"cost" 500
+	"map" 2
+
+outfitter "Common Outfits"
+	"Local Map"
+
+planet "Station"
+	description "A station."
+	spaceport "A spaceport."
+
+system Neighbour
+	pos 200 0
+	government Independent
+	habitable 100
+	link Home
+	link Outpost
+	object "Station"
+		period 10
+
+system Outpost
+	pos 400 0
+	government Independent
+	habitable 100
+	link Neighbour
+	link Frontier
+
+system Frontier
+	pos 600 0
+	government Independent
+	habitable 100
+	link Outpost
+"#;
+
+    fn game_data() -> ESGameLoader {
+        let system = format!("{}{}", test_data::SYSTEM, HOME);
+        test_data::data_from(&[
+            test_data::SHIPS,
+            test_data::PLANET,
+            &system,
+            test_data::START,
+            DATA,
+        ])
+    }
+
+    fn game() -> Game {
+        game_data().create_game().unwrap()
+    }
+
+    #[test]
+    fn can_explore_by_visiting_systems_and_planets() {
+        let mut game = game();
+        assert!(game.is_visited_system("Home"));
+        assert!(game.player.exploration.has_visited_planet("Home"));
+        assert!(game.is_known_system("Neighbour"));
+        assert!(!game.is_visited_system("Neighbour"));
+        assert!(!game.is_known_system("Outpost"));
+
+        game.take_off();
+        game.jump_to("Neighbour").unwrap();
+        assert!(game.is_visited_system("Neighbour"));
+        assert!(game.is_known_system("Outpost"));
+        assert!(!game.is_known_system("Frontier"));
+
+        game.land("Station", &mut rand::rngs::mock::StepRng::new(0, 0))
+            .unwrap();
+        assert!(game.player.exploration.has_visited_planet("Station"));
+    }
+
+    #[test]
+    fn can_reveal_systems_with_maps() {
+        let mut game = game();
+        assert_eq!(
+            game.buy_outfit("Local Map", 2),
+            Err(TradeError::OnlyOne(String::from("Local Map")))
+        );
+        assert_eq!(game.player.account.credits, 10_000);
+        assert_eq!(game.buy_outfit("Local Map", 1), Ok(500));
+        assert_eq!(game.player.account.credits, 9500);
+        assert!(game
+            .player
+            .flagship()
+            .outfits
+            .iter()
+            .all(|(outfit, _)| outfit.name != "Local Map"));
+        assert!(game.is_known_system("Outpost"));
+        assert!(!game.is_visited_system("Outpost"));
+        assert!(!game.is_known_system("Frontier"));
+    }
+
+    #[test]
+    fn can_save_and_load_the_exploration() {
+        let game_data = game_data();
+        let mut game = game_data.create_game().unwrap();
+        game.reveal_map(3);
+
+        let saved = SavedGame::from_game(&game);
+        let loaded = game_data.load_game(&saved).unwrap();
+        assert_eq!(loaded.player.exploration, game.player.exploration);
+        assert!(loaded.is_known_system("Frontier"));
+
+        let pilot = saved.to_es_pilot("Bob", "Smith");
+        let pilot = es_data_parser::parse_pilot(&pilot).unwrap();
+        assert_eq!(pilot.visited_systems, vec!["Home"]);
+        assert_eq!(pilot.visited_planets, vec!["Home"]);
+        let imported = game_data
+            .load_game(&SavedGame::from_es_pilot(&pilot).unwrap())
+            .unwrap();
+        assert!(imported.is_visited_system("Home"));
+        assert!(imported.is_known_system("Neighbour"));
+    }
+}
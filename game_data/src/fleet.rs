@@ -0,0 +1,206 @@
+use super::data::{Player, ShipInstance};
+use super::FleetError;
+
+impl Player {
+    /// the ship the player is flying
+    pub fn flagship(&self) -> &ShipInstance {
+        &self.fleet[self.flagship]
+    }
+
+    /// the ship the player is flying
+    pub fn flagship_mut(&mut self) -> &mut ShipInstance {
+        &mut self.fleet[self.flagship]
+    }
+
+    /// ships following the flagship
+    pub fn escorts(&self) -> impl Iterator<Item = (usize, &ShipInstance)> {
+        let flagship = self.flagship;
+        self.fleet
+            .iter()
+            .enumerate()
+            .filter(move |(index, ship)| *index != flagship && !ship.is_parked())
+    }
+
+    /// ships parked on a planet
+    pub fn parked_ships<'a>(
+        &'a self,
+        planet: &'a str,
+    ) -> impl Iterator<Item = (usize, &'a ShipInstance)> {
+        self.fleet
+            .iter()
+            .enumerate()
+            .filter(move |(_, ship)| ship.parked_on.as_deref() == Some(planet))
+    }
+
+    fn landed_planet_name(&self) -> Result<String, FleetError> {
+        self.current_planet
+            .as_ref()
+            .map(|planet| planet.name.clone())
+            .ok_or(FleetError::NotLanded)
+    }
+
+    /// check that a ship is with the player, either following it or parked where it's landed
+    fn check_ship_is_here(&self, index: usize) -> Result<(), FleetError> {
+        let ship = self.fleet.get(index).ok_or(FleetError::NoSuchShip(index))?;
+        match &ship.parked_on {
+            Some(planet) if self.landed_planet_name().ok().as_ref() != Some(planet) => {
+                Err(FleetError::ParkedElsewhere(planet.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Park a ship on the planet the player is landed on
+    pub fn park_ship(&mut self, index: usize) -> Result<(), FleetError> {
+        let planet = self.landed_planet_name()?;
+        self.check_ship_is_here(index)?;
+        if index == self.flagship {
+            return Err(FleetError::IsFlagship);
+        }
+        self.fleet[index].parked_on = Some(planet);
+        Ok(())
+    }
+
+    /// Take back a ship parked on the planet the player is landed on
+    pub fn unpark_ship(&mut self, index: usize) -> Result<(), FleetError> {
+        self.landed_planet_name()?;
+        self.check_ship_is_here(index)?;
+        self.fleet[index].parked_on = None;
+        Ok(())
+    }
+
+    /// Fly another ship, which must be with the player
+    pub fn set_flagship(&mut self, index: usize) -> Result<(), FleetError> {
+        self.check_ship_is_here(index)?;
+        self.fleet[index].parked_on = None;
+        self.flagship = index;
+        Ok(())
+    }
+
+    /// Remove a ship from the fleet, which must be with the player and not be the flagship
+    pub fn remove_ship(&mut self, index: usize) -> Result<ShipInstance, FleetError> {
+        self.check_ship_is_here(index)?;
+        if index == self.flagship {
+            return Err(FleetError::IsFlagship);
+        }
+        if index < self.flagship {
+            self.flagship -= 1;
+        }
+        Ok(self.fleet.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_data;
+    use crate::Game;
+
+    const DATA: &str = r#"outfit "Cabin"
+	category "Systems"
+	"mass" 5
+	"outfit space" -5
+	"bunks" 2
+	"maintenance costs" 50
+
+planet "Away"
+	description "Another planet."
+
+start
+	system "Home"
+	planet "Home"
+	date 16 11 3013
+	set "license: Pilot's"
+	ship "Shuttle"
+		name "Buttercup"
+	ship "Shuttle"
+	account
+		credits 10000
+		score 400
+		mortgage Mortgage
+			principal 0
+			interest 0.004
+			term 365
+"#;
+
+    /// a game starting with two shuttles with a cabin, the flagship being named "Buttercup"
+    fn game() -> Game {
+        let mut game = test_data::game_with(DATA);
+        let cabin = game
+            .outfits
+            .iter()
+            .find(|outfit| outfit.name == "Cabin")
+            .unwrap()
+            .clone();
+        for ship in &mut game.player.fleet {
+            ship.outfits.push(((*cabin).clone(), 1));
+        }
+        game
+    }
+
+    #[test]
+    fn can_start_with_the_ships_of_the_start_block() {
+        let game = game();
+
+        assert_eq!(game.player.fleet.len(), 2);
+        let flagship = game.player.flagship();
+        assert_eq!(flagship.name, "Buttercup");
+        assert_eq!(flagship.model.name, "Shuttle");
+        assert_eq!(flagship.hull, 600.0);
+        assert_eq!(flagship.shields, 0.0);
+        assert_eq!(flagship.fuel, 400.0);
+        assert_eq!(game.player.fleet[1].name, "Shuttle");
+        assert_eq!(game.player.escorts().count(), 1);
+        assert_eq!(game.get_daily_salaries(), 100);
+        assert_eq!(game.get_daily_maintenance(), 100);
+    }
+
+    #[test]
+    fn can_park_ships_and_change_flagship() {
+        let mut game = game();
+        let away = game
+            .planets
+            .iter()
+            .find(|planet| planet.name == "Away")
+            .unwrap()
+            .clone();
+
+        assert_eq!(game.player.park_ship(0), Err(FleetError::IsFlagship));
+        game.player.park_ship(1).unwrap();
+        assert_eq!(game.player.escorts().count(), 0);
+        assert_eq!(game.player.parked_ships("Home").count(), 1);
+        assert_eq!(game.get_daily_salaries(), 0);
+        assert_eq!(game.get_daily_maintenance(), 50);
+        let payments = game.pay_daily_finances();
+        assert_eq!(payments.salaries, 0);
+        assert_eq!(payments.maintenance, 50);
+        assert_eq!(game.player.account.credits, 10000 - 50);
+
+        game.player.current_planet = Some(away);
+        assert_eq!(
+            game.player.set_flagship(1),
+            Err(FleetError::ParkedElsewhere(String::from("Home")))
+        );
+        assert_eq!(
+            game.player.unpark_ship(1),
+            Err(FleetError::ParkedElsewhere(String::from("Home")))
+        );
+        assert_eq!(game.player.set_flagship(2), Err(FleetError::NoSuchShip(2)));
+
+        game.player.current_planet = None;
+        assert_eq!(game.player.park_ship(1), Err(FleetError::NotLanded));
+        game.player.current_planet = game
+            .planets
+            .iter()
+            .find(|planet| planet.name == "Home")
+            .cloned();
+        game.player.set_flagship(1).unwrap();
+        assert_eq!(game.player.flagship().name, "Shuttle");
+        assert!(!game.player.flagship().is_parked());
+
+        let removed = game.player.remove_ship(0).unwrap();
+        assert_eq!(removed.name, "Buttercup");
+        assert_eq!(game.player.flagship, 0);
+        assert_eq!(game.player.flagship().name, "Shuttle");
+    }
+}
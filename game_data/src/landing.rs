@@ -0,0 +1,416 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use super::data::{Object, Planet};
+use super::missions::MissionUpdate;
+use super::{Game, LandingError};
+
+/// What happened when the player landed on a planet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Landing {
+    /// was the fleet scanned for illegal outfits
+    pub scanned: bool,
+    /// fine given for the illegal outfits found, added to the debts of the player
+    pub fine: i64,
+    /// missions completed, failed or visited
+    pub missions: Vec<MissionUpdate>,
+}
+
+impl Game {
+    /// Find a planet of the current system by it's name
+    pub fn planet_in_system(&self, name: &str) -> Result<Arc<Planet>, LandingError> {
+        let planet = self
+            .planets
+            .iter()
+            .find(|planet| planet.name == name)
+            .ok_or_else(|| LandingError::UnknownPlanet(String::from(name)))?;
+        if !self.player.current_system.planets().contains(&name) {
+            return Err(LandingError::NotInSystem(String::from(name)));
+        }
+        Ok(planet.clone())
+    }
+
+    /// Find the planet a stellar object is, if it's one defined in the data files
+    pub fn object_planet(&self, object: &Object) -> Option<&Arc<Planet>> {
+        object
+            .planet
+            .as_ref()
+            .and_then(|name| self.planets.iter().find(|planet| &planet.name == name))
+    }
+
+    /// name of the government of a planet of the current system
+    fn landing_government<'a>(&'a self, planet: &'a Planet) -> &'a str {
+        planet
+            .government
+            .as_ref()
+            .unwrap_or(&self.player.current_system.government)
+    }
+
+    /// Check if the player is allowed to land on a planet of the current system. Anyone can land
+    /// on an uninhabited planet.
+    pub fn landing_permission(&self, name: &str) -> Result<(), LandingError> {
+        let planet = self.planet_in_system(name)?;
+        if !planet.is_inhabited() {
+            return Ok(());
+        }
+        if self
+            .player
+            .bribed_planets
+            .iter()
+            .any(|bribed| bribed == name)
+        {
+            return Ok(());
+        }
+        let government = self.landing_government(&planet);
+        let reputation = self.reputation(government);
+        if let Some(needed) = planet.required_reputation {
+            if reputation < needed {
+                return Err(LandingError::NotEnoughReputation {
+                    needed,
+                    available: reputation,
+                });
+            }
+        }
+        if self.is_hostile(government) {
+            return Err(LandingError::Hostile(
+                self.get_government(government)
+                    .map(|government| government.display_name.clone())
+                    .unwrap_or_else(|| String::from(government)),
+            ));
+        }
+        Ok(())
+    }
+
+    /// value of the ships following the player, with their outfits
+    pub fn fleet_value(&self) -> i64 {
+        self.player
+            .fleet
+            .iter()
+            .filter(|ship| !ship.is_parked())
+            .map(|ship| ship.get_cost())
+            .sum()
+    }
+
+    /// credits asked to be allowed to land on a planet, rounded up to the thousand
+    pub fn bribe_cost(&self, name: &str) -> Result<i64, LandingError> {
+        let planet = self.planet_in_system(name)?;
+        if planet.bribe <= 0.0 {
+            return Err(LandingError::NoBribe(String::from(name)));
+        }
+        let credits = (self.fleet_value() as f32 * planet.bribe) as i64;
+        Ok(((credits + 999) / 1000).max(1) * 1000)
+    }
+
+    /// Pay a bribe to be allowed to land on a planet until the player leaves the system.
+    /// Returns the credits paid.
+    pub fn bribe(&mut self, name: &str) -> Result<i64, LandingError> {
+        let cost = self.bribe_cost(name)?;
+        if self.player.account.credits < cost {
+            return Err(LandingError::NotEnoughCredits {
+                needed: cost,
+                available: self.player.account.credits,
+            });
+        }
+        self.player.account.credits -= cost;
+        self.player.bribed_planets.push(String::from(name));
+        Ok(cost)
+    }
+
+    /// fine given by a planet if it finds the illegal outfits installed or carried by the fleet
+    pub fn illegal_fine(&self, name: &str) -> Result<i64, LandingError> {
+        let planet = self.planet_in_system(name)?;
+        let multiplier = self
+            .get_government(self.landing_government(&planet))
+            .map(|government| government.fine)
+            .unwrap_or(1.0);
+        let illegal: f32 = self
+            .player
+            .fleet
+            .iter()
+            .filter(|ship| !ship.is_parked())
+            .flat_map(|ship| ship.outfits.iter().chain(ship.cargo.outfits.iter()))
+            .map(|(outfit, count)| outfit.illegal * (*count as f32))
+            .sum();
+        Ok((illegal * multiplier) as i64)
+    }
+
+    /// Land on a planet of the current system, where the fleet is refueled and repaired if it
+    /// has a spaceport. The fleet may be scanned depending on the security of the planet, and
+    /// fined for it's illegal outfits. Active missions are checked, and new ones offered.
+    pub fn land<R: Rng>(&mut self, name: &str, rng: &mut R) -> Result<Landing, LandingError> {
+        self.landing_permission(name)?;
+        let planet = self.planet_in_system(name)?;
+
+        let scanned = rng.gen::<f32>() < planet.security;
+        let fine = if scanned { self.illegal_fine(name)? } else { 0 };
+        if fine > 0 {
+            self.player.account.add_fine(fine);
+        }
+
+        self.player.exploration.visit_planet(name);
+        if planet.has_spaceport() {
+            self.player
+                .fleet
+                .iter_mut()
+                .filter(|ship| !ship.is_parked())
+                .for_each(|ship| ship.restore());
+        }
+        self.player.current_planet = Some(planet);
+        let missions = self.update_missions_on_landing();
+        self.generate_mission_offers(rng);
+        Ok(Landing {
+            scanned,
+            fine,
+            missions,
+        })
+    }
+
+    /// Leave the planet the player is landed on
+    pub fn take_off(&mut self) {
+        self.player.current_planet = None;
+        self.player.missions.clear_offers();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::mock::StepRng;
+
+    use super::*;
+    use crate::test_data;
+
+    const GOVERNMENTS: &str = r#"government "Republic"
+	"player reputation" 10
+
+government "Pirate"
+	"player reputation" -10
+	fine 2
+"#;
+
+    const DEN: &str = r#"planet "Den"
+	attributes pirate
+	government "Pirate"
+	description "A pirate den."
+	spaceport "A den."
+	landscape land/sky1
+	bribe .05
+	security 1
+"#;
+
+    const FORTRESS: &str = r#"planet "Fortress"
+	description "A fortress."
+	"required reputation" 20
+	bribe 0
+	tribute 1500
+		threshold 4000
+		fleet "Large Republic" 10
+
+planet "Wreck"
+	government "Pirate"
+	description "A pirate wreck."
+"#;
+
+    /// game in a "Home" system with the given stellar objects, with the player in space
+    fn game(objects: &str, data: &str) -> Game {
+        let system = format!("{}{}", test_data::SYSTEM, objects);
+        let mut game = test_data::game_from(&[
+            test_data::SHIPS,
+            test_data::PLANET,
+            &system,
+            test_data::START,
+            GOVERNMENTS,
+            data,
+        ]);
+        game.take_off();
+        game
+    }
+
+    /// game with "Den" orbiting "Home", "Fortress" and "Wreck"
+    fn den_game() -> Game {
+        game(
+            "\tobject \"Home\"\n\t\tperiod 10\n\t\tobject \"Den\"\n\t\t\tperiod 1\n\
+             \tobject \"Fortress\"\n\t\tperiod 100\n\tobject \"Wreck\"\n\t\tperiod 200\n",
+            &format!("{}{}", DEN, FORTRESS),
+        )
+    }
+
+    #[test]
+    fn can_find_planets_of_the_system() {
+        let game = game(
+            "\tobject \"Home\"\n\t\tperiod 10\n\tobject \"Den\"\n\t\tperiod 100\n",
+            &format!("{}planet \"Away\"\n\tdescription \"Far away.\"\n", DEN),
+        );
+        assert_eq!(game.player.current_system.planets(), vec!["Home", "Den"]);
+        let home = game.planet_in_system("Home").unwrap();
+        assert_eq!(home.description, "A planet.");
+        assert_eq!(home.spaceport, "A spaceport.");
+        let den = game.planet_in_system("Den").unwrap();
+        assert_eq!(den.landscape.as_deref(), Some("land/sky1"));
+        assert_eq!(
+            game.planet_in_system("Away")
+                .map(|planet| planet.name.clone()),
+            Err(LandingError::NotInSystem(String::from("Away")))
+        );
+        assert_eq!(
+            game.planet_in_system("Nowhere")
+                .map(|planet| planet.name.clone()),
+            Err(LandingError::UnknownPlanet(String::from("Nowhere")))
+        );
+    }
+
+    #[test]
+    fn can_tell_which_objects_are_landable_planets() {
+        let game = game(
+            "\tobject\n\t\tsprite star/g0\n\t\tperiod 10\n\
+             \tobject \"Home\"\n\t\tperiod 100\n\t\tobject \"Den\"\n\t\t\tperiod 10\n\
+             \tobject \"Fortress\"\n\t\tperiod 300\n\t\toffset 90\n\
+             \tobject \"Ghost\"\n\t\tperiod 1000\n",
+            &format!("{}{}", DEN, FORTRESS),
+        );
+        let objects = &game.player.current_system.objects;
+        assert!(!objects[0].landable);
+        assert!(game.object_planet(&objects[0]).is_none());
+        assert!(objects[1].landable);
+        assert!(objects[1].objects[0].landable);
+        assert_eq!(objects[2].offset, 90.0);
+        assert!(!objects[3].landable);
+        assert!(game.object_planet(&objects[3]).is_none());
+
+        let home = game.object_planet(&objects[1]).unwrap();
+        assert!(home.has_spaceport());
+        assert!(!home.has_shipyard());
+        assert!(home.is_inhabited());
+        assert!(home.tribute.is_none());
+
+        let den = game.object_planet(&objects[1].objects[0]).unwrap();
+        assert!(den.has_attribute("pirate"));
+
+        let fortress = game.object_planet(&objects[2]).unwrap();
+        assert!(fortress.is_inhabited());
+        let tribute = fortress.tribute.as_ref().unwrap();
+        assert_eq!(tribute.value, 1500);
+        assert_eq!(tribute.threshold, 4000);
+        assert_eq!(tribute.fleet, "Large Republic");
+        assert_eq!(tribute.fleet_count, 10);
+    }
+
+    #[test]
+    fn can_only_land_with_permission() {
+        let game = den_game();
+        assert_eq!(game.landing_permission("Home"), Ok(()));
+        // uninhabited, so it's hostile government doesn't matter
+        assert_eq!(game.landing_permission("Wreck"), Ok(()));
+        assert_eq!(
+            game.landing_permission("Fortress"),
+            Err(LandingError::NotEnoughReputation {
+                needed: 20.0,
+                available: 10.0,
+            })
+        );
+        assert_eq!(
+            game.landing_permission("Den"),
+            Err(LandingError::Hostile(String::from("Pirate")))
+        );
+    }
+
+    #[test]
+    fn can_bribe_a_planet_to_land() {
+        let mut game = den_game();
+        assert_eq!(game.fleet_value(), 100_000);
+        assert_eq!(
+            game.bribe("Fortress"),
+            Err(LandingError::NoBribe(String::from("Fortress")))
+        );
+        assert_eq!(game.bribe_cost("Den"), Ok(5000));
+
+        game.player.account.credits = 4000;
+        assert_eq!(
+            game.bribe("Den"),
+            Err(LandingError::NotEnoughCredits {
+                needed: 5000,
+                available: 4000,
+            })
+        );
+        game.player.account.credits = 10_000;
+        assert_eq!(game.bribe("Den"), Ok(5000));
+        assert_eq!(game.player.account.credits, 5000);
+        assert_eq!(game.landing_permission("Den"), Ok(()));
+    }
+
+    #[test]
+    fn can_land_to_refuel_and_repair() {
+        let mut game = den_game();
+        game.player.flagship_mut().fuel = 100.0;
+        game.player.flagship_mut().hull = 1.0;
+
+        let landing = game.land("Home", &mut StepRng::new(0, 0)).unwrap();
+        assert_eq!(
+            landing,
+            Landing {
+                scanned: true,
+                fine: 0,
+                missions: vec![],
+            }
+        );
+        assert_eq!(game.player.current_planet.as_ref().unwrap().name, "Home");
+        assert_eq!(game.player.flagship().fuel, 400.0);
+        assert_eq!(game.player.flagship().hull, 600.0);
+
+        game.take_off();
+        assert!(game.player.current_planet.is_none());
+        assert!(game.land("Den", &mut StepRng::new(0, 0)).is_err());
+        assert!(game.player.current_planet.is_none());
+    }
+
+    #[test]
+    fn can_only_refuel_and_repair_with_a_spaceport() {
+        let mut game = game(
+            "\tobject \"Home\"\n\t\tperiod 10\n\tobject \"Rock\"\n\t\tperiod 100\n",
+            "planet \"Rock\"\n\tdescription \"A rock.\"\n",
+        );
+        game.player.flagship_mut().fuel = 100.0;
+        game.player.flagship_mut().hull = 1.0;
+
+        game.land("Rock", &mut StepRng::new(0, 0)).unwrap();
+        assert_eq!(game.player.current_planet.as_ref().unwrap().name, "Rock");
+        assert_eq!(game.player.flagship().fuel, 100.0);
+        assert_eq!(game.player.flagship().hull, 1.0);
+    }
+
+    #[test]
+    fn can_be_fined_for_illegal_outfits() {
+        let mut game = game(
+            "\tobject \"Home\"\n\t\tperiod 10\n\tobject \"Den\"\n\t\tperiod 100\n",
+            &format!(
+                "{}outfit \"Stolen Goods\"\n\tcategory \"Special\"\n\t\"illegal\" 500\n",
+                DEN
+            ),
+        );
+        assert_eq!(game.illegal_fine("Home"), Ok(0));
+        let stolen_goods = game
+            .outfits
+            .iter()
+            .find(|outfit| outfit.name == "Stolen Goods")
+            .unwrap()
+            .clone();
+        game.player
+            .flagship_mut()
+            .cargo
+            .add_outfit(&stolen_goods, 1);
+        assert_eq!(game.illegal_fine("Home"), Ok(500));
+        assert_eq!(game.illegal_fine("Den"), Ok(1000));
+
+        game.bribe("Den").unwrap();
+        let landing = game.land("Den", &mut StepRng::new(0, 0)).unwrap();
+        assert_eq!(
+            landing,
+            Landing {
+                scanned: true,
+                fine: 1000,
+                missions: vec![],
+            }
+        );
+        assert_eq!(game.player.account.total_debt(), 1000);
+    }
+}
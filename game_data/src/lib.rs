@@ -13,12 +13,50 @@
 
 use std::sync::Arc;
 
+use helpers::max;
+
+mod errors;
+pub use errors::{
+    FleetError, GameCreationError, InstallError, JumpError, LandingError, MissionError, SaveError,
+    Space, TradeError,
+};
 mod loader;
 pub use loader::ESGameLoader;
 mod state;
 pub use state::State;
+/// account of the player, and how it's debts are paid
+pub mod account;
+/// cargo carried by ships, and trading commodities
+pub mod cargo;
+/// conditions of the player, and how to test and change them
+pub mod conditions;
+/// systems run each day, and how time passes
+pub mod daily;
 /// data types that represent a game
 pub mod data;
+/// systems and planets known by the player
+pub mod exploration;
+/// ships owned by the player
+pub mod fleet;
+/// landing on planets, and what happens when landed
+pub mod landing;
+/// missions offered to the player, and how they are completed
+pub mod missions;
+/// jumps between systems, and routes across the galaxy
+pub mod navigation;
+/// installing and removing outfits on ships
+pub mod outfitting;
+/// governments, and the reputation of the player with them
+pub mod politics;
+/// saving a game to a file, and loading it back
+pub mod save;
+/// buying and selling ships and outfits on planets
+pub mod shop;
+/// stats of ships, computed from their installed outfits
+pub mod stats;
+/// data files shared by the tests, each test adding the few objects it needs
+#[cfg(test)]
+mod test_data;
 mod unresolved_data;
 
 /// A Game
@@ -30,8 +68,21 @@ pub struct Game {
     pub player: data::Player,
     /// the list of systems
     pub systems: Vec<Arc<data::System>>,
+    /// the list of galaxies, drawn behind the systems on the map
+    pub galaxies: Vec<Arc<data::Galaxy>>,
     /// the list of ships
-    pub ships: Vec<Arc<data::Ship>>,
+    pub ships: Vec<Arc<data::ShipModel>>,
+    /// the list of outfits
+    pub outfits: Vec<Arc<data::Outfit>>,
+    /// the list of governments
+    pub governments: Vec<Arc<data::Government>>,
+    /// the list of planets
+    pub planets: Vec<Arc<data::Planet>>,
+    /// the list of missions
+    pub missions: Vec<Arc<missions::Mission>>,
+    /// the player conditions
+    pub conditions: conditions::ConditionStore,
+    daily_systems: Vec<daily::DailySystem>,
 }
 
 #[allow(clippy::new_without_default)]
@@ -45,4 +96,35 @@ impl Game {
     pub fn get_nb_days_elapsed_since(&self, start_date: chrono::NaiveDate) -> i64 {
         (self.current_date - start_date).num_days()
     }
+
+    /// Daily salaries of the crew of the ships following the player, the captain not being paid
+    pub fn get_daily_salaries(&self) -> i64 {
+        let required_crew: f32 = self
+            .player
+            .fleet
+            .iter()
+            .filter(|ship| !ship.is_parked())
+            .map(|ship| ship.stats().required_crew)
+            .sum();
+        account::CREW_SALARY * max!(required_crew as i64 - 1, 0)
+    }
+
+    /// Daily maintenance costs of the ships following the player and their outfits
+    pub fn get_daily_maintenance(&self) -> i64 {
+        let maintenance: f32 = self
+            .player
+            .fleet
+            .iter()
+            .filter(|ship| !ship.is_parked())
+            .map(|ship| ship.stats().maintenance_costs)
+            .sum();
+        maintenance as i64
+    }
+
+    /// Pay the daily crew salaries, maintenance, mortgages and fines of the player
+    pub fn pay_daily_finances(&mut self) -> account::DailyPayments {
+        let salaries = self.get_daily_salaries();
+        let maintenance = self.get_daily_maintenance();
+        self.player.account.step(salaries, maintenance)
+    }
 }
@@ -3,42 +3,118 @@ use std::sync::Arc;
 use rand::seq::IteratorRandom;
 
 use super::data::*;
+use super::save::{SavedGame, SavedShip, SAVE_VERSION};
+use super::{GameCreationError, SaveError};
 
 /// resolved game data
 #[derive(Debug)]
 pub struct ESGameLoader {
     outfits: Vec<Outfit>,
-    ships: Vec<Ship>,
+    ships: Vec<ShipModel>,
     systems: Vec<System>,
-    start: Option<(String, (i32, u32, u32))>,
+    galaxies: Vec<Galaxy>,
+    planets: Vec<Planet>,
+    governments: Vec<Government>,
+    missions: Vec<crate::missions::Mission>,
+    start: Option<Start>,
+    warnings: Vec<String>,
 }
 
-/// Helper to load es data files and create a game
-#[derive(Debug)]
-pub struct UnresolvedESGameLoader {
+/// Start point of a new game
+#[derive(Debug, Clone)]
+struct Start {
+    system: String,
+    planet: Option<String>,
+    ships: Vec<(String, Option<String>)>,
+    date: (i32, u32, u32),
+    account: crate::account::Account,
+}
+
+/// lists of ships or outfits sold together, by name
+type Sales = Vec<(String, Vec<String>)>;
+
+/// Data loaded from a single es data file
+#[derive(Debug, Default)]
+struct FileData {
     outfits: Vec<Outfit>,
     ships: Vec<super::unresolved_data::Ship>,
     systems: Vec<System>,
-    start: Option<(String, (i32, u32, u32))>,
+    galaxies: Vec<Galaxy>,
+    planets: Vec<super::unresolved_data::Planet>,
+    shipyards: Sales,
+    outfitters: Sales,
+    governments: Vec<Government>,
+    missions: Vec<crate::missions::Mission>,
+    start: Option<Start>,
+}
+
+/// Helper to load es data files and create a game
+#[derive(Debug)]
+pub struct UnresolvedESGameLoader {
+    /// data by the file it was loaded from, in loading order
+    files: Vec<(String, FileData)>,
+}
+
+fn es_government_to_government(government: &es_data_parser::Government) -> Government {
+    Government {
+        name: String::from(government.name),
+        display_name: String::from(government.display_name.unwrap_or(government.name)),
+        color: match government.color {
+            Some(es_data_parser::Color::Rgb(red, green, blue)) => Some((red, green, blue)),
+            _ => None,
+        },
+        initial_reputation: government.player_reputation.unwrap_or(0.0),
+        attitudes: government
+            .attitudes
+            .iter()
+            .map(|(name, attitude)| (String::from(*name), *attitude))
+            .collect(),
+        penalties: crate::politics::ShipEvent::ALL
+            .iter()
+            .filter_map(|event| {
+                government
+                    .penalties
+                    .iter()
+                    .find(|(name, _)| *name == event.name())
+                    .map(|(_, penalty)| (*event, *penalty))
+            })
+            .collect(),
+        bribe: government.bribe.unwrap_or(0.0),
+        fine: government.fine.unwrap_or(1.0),
+    }
 }
 
 fn es_object_to_object<'a>(object: &es_data_parser::SystemObject<'a>) -> Object {
     Object {
+        planet: object.name.map(String::from),
+        landable: false,
         sprite: object.sprite.map(String::from),
         distance: object.distance.unwrap_or(0.0),
         period: object.period,
+        offset: object.offset.unwrap_or(0.0),
         objects: object.objects.iter().map(es_object_to_object).collect(),
     }
 }
 
+fn mark_landable(objects: &mut [Object], planets: &[Planet]) {
+    for object in objects {
+        object.landable = object
+            .planet
+            .as_ref()
+            .map(|name| planets.iter().any(|planet| &planet.name == name))
+            .unwrap_or(false);
+        mark_landable(&mut object.objects, planets);
+    }
+}
+
 fn outfit_as_engine(outfit: &es_data_parser::Outfit) -> Vec<OutfitEngine> {
     let mut engines = vec![];
     if outfit.thrust.is_some() {
         engines.push(OutfitEngine {
             ty: EngineType::Thrust,
             power: outfit.thrust.unwrap_or(0.0),
-            energy_consumption: outfit.energy_consumption.unwrap_or(0.0),
-            heat_generation: outfit.heat_generation.unwrap_or(0.0),
+            energy_consumption: outfit.thrusting_energy.unwrap_or(0.0),
+            heat_generation: outfit.thrusting_heat.unwrap_or(0.0),
         })
     }
     if outfit.turn.is_some() {
@@ -60,23 +136,134 @@ fn outfit_as_engine(outfit: &es_data_parser::Outfit) -> Vec<OutfitEngine> {
     engines
 }
 
+fn outfit_as_attributes(outfit: &es_data_parser::Outfit) -> Attributes {
+    Attributes {
+        energy_capacity: outfit.energy_capacity.unwrap_or(0.0),
+        energy_generation: outfit.energy_generation.unwrap_or(0.0),
+        energy_consumption: outfit.energy_consumption.unwrap_or(0.0),
+        solar_collection: outfit.solar_collection.unwrap_or(0.0),
+        heat_generation: outfit.heat_generation.unwrap_or(0.0),
+        cooling: outfit.cooling.unwrap_or(0.0),
+        heat_dissipation: outfit.heat_dissipation.unwrap_or(0.0),
+        shield_generation: outfit.shield_generation.unwrap_or(0.0),
+        shield_energy: outfit.shield_energy.unwrap_or(0.0),
+        hull_repair_rate: outfit.hull_repair_rate.unwrap_or(0.0),
+        hull_energy: outfit.hull_energy.unwrap_or(0.0),
+        cargo_space: outfit.cargo_space.unwrap_or(0.0),
+        outfit_space: outfit.outfit_space,
+        weapon_capacity: outfit.weapon_capacity.unwrap_or(0.0),
+        engine_capacity: outfit.engine_capacity.unwrap_or(0.0),
+        gun_ports: outfit.gun_ports.unwrap_or(0.0),
+        turret_mounts: outfit.turret_mounts.unwrap_or(0) as f32,
+        fuel_capacity: outfit.fuel_capacity.unwrap_or(0.0),
+        ramscoop: outfit.ramscoop.unwrap_or(0.0),
+        bunks: outfit.bunks.unwrap_or(0.0),
+        required_crew: outfit.required_crew.unwrap_or(0.0),
+        maintenance_costs: outfit.maintenance_costs.unwrap_or(0.0),
+        ..Default::default()
+    }
+}
+
+fn outfit_as_ammo_capacity(outfit: &es_data_parser::Outfit) -> Vec<(String, f32)> {
+    [
+        ("gatling round", outfit.gatling_round_capacity),
+        ("javelin", outfit.javelin_capacity),
+        ("meteor", outfit.meteor_capacity),
+        ("rocket", outfit.rocket_capacity),
+        ("sidewinder", outfit.sidewinder_capacity),
+        ("torpedo", outfit.torpedo_capacity),
+        ("typhoon", outfit.typhoon_capacity),
+    ]
+    .iter()
+    .filter_map(|(kind, capacity)| capacity.map(|capacity| (String::from(*kind), capacity as f32)))
+    .collect()
+}
+
+fn ship_as_attributes(ship: &es_data_parser::Ship) -> Attributes {
+    Attributes {
+        heat_dissipation: ship.attributes.heat_dissipation,
+        shields: ship.attributes.shields,
+        hull: ship.attributes.hull,
+        cargo_space: ship.attributes.cargo_space,
+        outfit_space: ship.attributes.outfit_space,
+        weapon_capacity: ship.attributes.weapon_capacity,
+        engine_capacity: ship.attributes.engine_capacity,
+        gun_ports: ship.gun.len() as f32,
+        turret_mounts: ship.turret.len() as f32,
+        fuel_capacity: ship.attributes.fuel_capacity,
+        bunks: ship.attributes.bunks,
+        required_crew: ship.attributes.required_crew,
+        maintenance_costs: ship.attributes.maintenance_costs,
+        ..Default::default()
+    }
+}
+
+fn sale_items(sale: &es_data_parser::Sale) -> (String, Vec<String>) {
+    (
+        String::from(sale.name),
+        sale.items.iter().map(|item| String::from(*item)).collect(),
+    )
+}
+
+fn outfit_as_drive(outfit: &es_data_parser::Outfit) -> Vec<OutfitDrive> {
+    let mut drives = vec![];
+    let mut add_drive = |ty: DriveType| {
+        drives.push(OutfitDrive {
+            ty,
+            fuel: outfit.jump_fuel.unwrap_or_else(|| ty.default_fuel()),
+            speed: outfit.jump_speed,
+        })
+    };
+    if outfit.hyperdrive.is_some() {
+        if outfit.scram_drive.is_some() {
+            add_drive(DriveType::ScramDrive);
+        } else {
+            add_drive(DriveType::Hyperdrive);
+        }
+    }
+    if outfit.jump_drive.is_some() {
+        add_drive(DriveType::JumpDrive);
+    }
+    drives
+}
+
 #[allow(clippy::new_without_default)]
 impl<'a> UnresolvedESGameLoader {
     /// Start an empty es game loader
     pub fn empty() -> Self {
-        Self {
-            outfits: vec![],
-            ships: vec![],
-            systems: vec![],
-            start: None,
-        }
+        Self { files: vec![] }
     }
 
     /// Load a game file
-    pub fn load(&mut self, es_game_data_source: &str) {
+    pub fn load(&mut self, file: &str, es_game_data_source: &str) {
+        self.files
+            .push((String::from(file), Self::parse_file(es_game_data_source)));
+    }
+
+    /// Remove everything a file contributed, and load it again from it's new content
+    pub fn reload(&mut self, file: &str, es_game_data_source: &str) {
+        let file_data = Self::parse_file(es_game_data_source);
+        if let Some(loaded) = self.files.iter_mut().find(|loaded| loaded.0 == file) {
+            loaded.1 = file_data;
+        } else {
+            self.files.push((String::from(file), file_data));
+        }
+    }
+
+    /// Remove everything a file contributed
+    pub fn unload(&mut self, file: &str) {
+        self.files.retain(|loaded| loaded.0 != file);
+    }
+
+    /// List of files loaded
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|loaded| loaded.0.as_str())
+    }
+
+    fn parse_file(es_game_data_source: &str) -> FileData {
         let es_game_data = es_data_parser::parse(es_game_data_source);
 
-        let mut outfits = es_game_data
+        let outfits = es_game_data
             .iter()
             .filter_map(|object| {
                 if let es_data_parser::Object::Outfit(outfit) = object {
@@ -105,11 +292,22 @@ impl<'a> UnresolvedESGameLoader {
                 },
                 mass: outfit.mass,
                 engine: outfit_as_engine(outfit),
+                flare_sprite: outfit.flare_sprite.as_ref().map(Sprite::from_es_sprite),
+                drive: outfit_as_drive(outfit),
+                attributes: outfit_as_attributes(outfit),
+                ammo_capacity: outfit_as_ammo_capacity(outfit),
+                cost: outfit.cost,
+                illegal: outfit.illegal.unwrap_or(0.0),
+                map: outfit.map.unwrap_or(0.0) as u32,
+                licenses: outfit
+                    .licenses
+                    .iter()
+                    .map(|license| String::from(*license))
+                    .collect(),
             })
             .collect::<Vec<_>>();
-        self.outfits.append(&mut outfits);
 
-        let mut ships = es_game_data
+        let ships = es_game_data
             .iter()
             .filter_map(|object| {
                 if let es_data_parser::Object::Ship(ship) = object {
@@ -120,10 +318,7 @@ impl<'a> UnresolvedESGameLoader {
             })
             .map(|ship| super::unresolved_data::Ship {
                 name: String::from(ship.name),
-                sprite: match ship.sprite {
-                    es_data_parser::Sprite::Simple(sprite) => String::from(sprite),
-                    es_data_parser::Sprite::Sprite { name, .. } => format!("{}=0", name),
-                },
+                sprite: Sprite::from_es_sprite(&ship.sprite),
                 outfits: ship
                     .outfits
                     .iter()
@@ -131,11 +326,18 @@ impl<'a> UnresolvedESGameLoader {
                     .collect(),
                 drag: ship.attributes.drag,
                 mass: ship.attributes.mass,
+                attributes: ship_as_attributes(ship),
+                cost: ship.attributes.cost,
+                licenses: ship
+                    .attributes
+                    .licenses
+                    .iter()
+                    .map(|license| String::from(*license))
+                    .collect(),
             })
             .collect::<Vec<_>>();
-        self.ships.append(&mut ships);
 
-        let mut systems = es_game_data
+        let systems = es_game_data
             .iter()
             .filter_map(|object| {
                 if let es_data_parser::Object::System(system) = object {
@@ -148,41 +350,151 @@ impl<'a> UnresolvedESGameLoader {
                 name: String::from(system.name),
                 objects: system.objects.iter().map(es_object_to_object).collect(),
                 position: euclid::vec2(system.pos.x, system.pos.y),
+                links: system
+                    .links
+                    .iter()
+                    .map(|link| String::from(*link))
+                    .collect(),
+                trades: system
+                    .trades
+                    .iter()
+                    .map(|trade| Trade {
+                        commodity: String::from(trade.name),
+                        base_price: i64::from(trade.price),
+                        price: i64::from(trade.price),
+                    })
+                    .collect(),
+                government: String::from(system.government),
             })
             .collect::<Vec<_>>();
-        self.systems.append(&mut systems);
 
-        if let Some(start) = es_game_data
+        let planets = es_game_data
             .iter()
             .filter_map(|object| {
+                if let es_data_parser::Object::Planet(planet) = object {
+                    Some(planet)
+                } else {
+                    None
+                }
+            })
+            .map(|planet| super::unresolved_data::Planet {
+                name: String::from(planet.name),
+                government: planet.government.map(String::from),
+                attributes: planet
+                    .attributes
+                    .iter()
+                    .map(|attribute| String::from(*attribute))
+                    .collect(),
+                shipyard: planet
+                    .shipyard
+                    .iter()
+                    .map(|name| String::from(*name))
+                    .collect(),
+                outfitter: planet
+                    .outfitter
+                    .iter()
+                    .map(|name| String::from(*name))
+                    .collect(),
+                description: planet.description.join("\n"),
+                spaceport: planet.spaceport.join("\n"),
+                landscape: planet.landscape.map(String::from),
+                bribe: planet.bribe.unwrap_or(Planet::DEFAULT_BRIBE),
+                security: planet.security.unwrap_or(Planet::DEFAULT_SECURITY),
+                required_reputation: planet.required_reputation,
+                tribute: planet.tribute.as_ref().map(|tribute| Tribute {
+                    value: i64::from(tribute.value),
+                    threshold: tribute.threshold,
+                    fleet: String::from(tribute.fleet.kind),
+                    fleet_count: u32::from(tribute.fleet.count),
+                }),
+            })
+            .collect::<Vec<_>>();
+
+        let mut shipyards = Sales::new();
+        let mut outfitters = Sales::new();
+        let mut governments = vec![];
+        let mut galaxies = vec![];
+        let mut missions = vec![];
+        for object in &es_game_data {
+            match object {
+                es_data_parser::Object::Shipyard(sale) => shipyards.push(sale_items(sale)),
+                es_data_parser::Object::Outfitter(sale) => outfitters.push(sale_items(sale)),
+                es_data_parser::Object::Government(government) => {
+                    governments.push(es_government_to_government(government))
+                }
+                es_data_parser::Object::Galaxy(galaxy) => galaxies.push(Galaxy {
+                    name: String::from(galaxy.name),
+                    position: euclid::vec2(galaxy.pos.x, galaxy.pos.y),
+                    sprite: galaxy.sprite.map(String::from),
+                }),
+                es_data_parser::Object::Mission(mission) => {
+                    missions.push(crate::missions::Mission::from_es_mission(mission))
+                }
+                _ => (),
+            }
+        }
+
+        let start = es_game_data
+            .iter()
+            .find_map(|object| {
                 if let es_data_parser::Object::Start(start) = object {
                     Some(start)
                 } else {
                     None
                 }
             })
-            .next()
-        {
-            self.start = Some((
-                String::from(start.system),
-                (start.date.year, start.date.month, start.date.day),
-            ));
+            .map(|start| Start {
+                system: String::from(start.system),
+                planet: if start.planet.is_empty() {
+                    None
+                } else {
+                    Some(String::from(start.planet))
+                },
+                ships: start
+                    .ships
+                    .iter()
+                    .map(|ship| (String::from(ship.model), ship.name.map(String::from)))
+                    .collect(),
+                date: (start.date.year, start.date.month, start.date.day),
+                account: crate::account::Account::from_es_account(&start.account),
+            });
+
+        FileData {
+            outfits,
+            ships,
+            systems,
+            galaxies,
+            planets,
+            shipyards,
+            outfitters,
+            governments,
+            missions,
+            start,
         }
     }
 
-    pub fn resolve(self) -> ESGameLoader {
-        let outfits = self.outfits;
+    /// Resolve references between all the loaded files
+    pub fn resolve(&self) -> ESGameLoader {
+        let outfits: Vec<Outfit> = self
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.outfits.iter().cloned())
+            .collect();
         let ships = self
-            .ships
-            .into_iter()
-            .map(|ship| Ship {
-                name: ship.name,
-                sprite: ship.sprite,
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.ships.iter())
+            .map(|ship| ShipModel {
+                name: ship.name.clone(),
+                sprite: ship.sprite.clone(),
                 drag: ship.drag,
                 base_mass: ship.mass,
+                attributes: ship.attributes,
+                cost: ship.cost,
+                licenses: ship.licenses.clone(),
                 outfits: ship
                     .outfits
-                    .into_iter()
+                    .iter()
                     .filter_map(|unresolved_outfit| {
                         outfits
                             .iter()
@@ -191,57 +503,628 @@ impl<'a> UnresolvedESGameLoader {
                     })
                     .collect(),
             })
+            .collect::<Vec<ShipModel>>();
+        let warnings = ships
+            .iter()
+            .flat_map(|ship| {
+                ShipInstance::new(&ship.name, Arc::new(ship.clone()))
+                    .check_outfits()
+                    .into_iter()
+                    .map(move |error| format!("ship '{}': {}", ship.name, error))
+            })
             .collect();
+        let sold = |lists: &[String], sales: fn(&FileData) -> &Sales| {
+            let mut items: Vec<String> = vec![];
+            for (_, sale_items) in self
+                .files
+                .iter()
+                .flat_map(|loaded| sales(&loaded.1).iter())
+                .filter(|(name, _)| lists.contains(name))
+            {
+                for item in sale_items {
+                    if !items.contains(item) {
+                        items.push(item.clone());
+                    }
+                }
+            }
+            items
+        };
+        let planets = self
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.planets.iter())
+            .map(|planet| Planet {
+                name: planet.name.clone(),
+                government: planet.government.clone(),
+                attributes: planet.attributes.clone(),
+                shipyard: sold(&planet.shipyard, |file| &file.shipyards),
+                outfitter: sold(&planet.outfitter, |file| &file.outfitters),
+                description: planet.description.clone(),
+                spaceport: planet.spaceport.clone(),
+                landscape: planet.landscape.clone(),
+                bribe: planet.bribe,
+                security: planet.security,
+                required_reputation: planet.required_reputation,
+                tribute: planet.tribute.clone(),
+            })
+            .collect::<Vec<_>>();
+        let systems = self
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.systems.iter().cloned())
+            .map(|mut system| {
+                mark_landable(&mut system.objects, &planets);
+                system
+            })
+            .collect();
+        let mut galaxies: Vec<Galaxy> = vec![];
+        for galaxy in self
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.galaxies.iter())
+        {
+            // a galaxy defined again by a later file replaces the first definition
+            match galaxies
+                .iter_mut()
+                .find(|existing| existing.name == galaxy.name)
+            {
+                Some(existing) => *existing = galaxy.clone(),
+                None => galaxies.push(galaxy.clone()),
+            }
+        }
+        let mut missions: Vec<crate::missions::Mission> = vec![];
+        for mission in self
+            .files
+            .iter()
+            .flat_map(|loaded| loaded.1.missions.iter())
+        {
+            // as for galaxies, a later definition replaces the first one
+            match missions
+                .iter_mut()
+                .find(|existing| existing.id == mission.id)
+            {
+                Some(existing) => *existing = mission.clone(),
+                None => missions.push(mission.clone()),
+            }
+        }
         ESGameLoader {
             outfits,
-            systems: self.systems,
-            start: self.start,
+            planets,
+            galaxies,
+            governments: self
+                .files
+                .iter()
+                .flat_map(|loaded| loaded.1.governments.iter().cloned())
+                .collect(),
+            missions,
+            systems,
+            start: self
+                .files
+                .iter()
+                .rev()
+                .find_map(|loaded| loaded.1.start.clone()),
             ships,
+            warnings,
         }
     }
 }
 
 impl ESGameLoader {
+    /// Find a ship by it's name
+    pub fn get_ship(&self, name: &str) -> Option<&ShipModel> {
+        self.ships.iter().find(|ship| ship.name == name)
+    }
+
+    /// Find an outfit by it's name
+    pub fn get_outfit(&self, name: &str) -> Option<&Outfit> {
+        self.outfits.iter().find(|outfit| outfit.name == name)
+    }
+
+    /// Problems found in the data files, that don't prevent playing
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Update a game with the current data, keeping what the player did
+    pub fn refresh_game(&self, game: &mut super::Game) {
+        let ships: Vec<Arc<ShipModel>> = self.ships.iter().cloned().map(Arc::new).collect();
+        let systems: Vec<Arc<System>> = self.systems.iter().cloned().map(Arc::new).collect();
+        let planets: Vec<Arc<Planet>> = self.planets.iter().cloned().map(Arc::new).collect();
+
+        for owned in game.player.fleet.iter_mut() {
+            if let Some(model) = ships.iter().find(|ship| ship.name == owned.model.name) {
+                owned.model = model.clone();
+            }
+            let refresh_outfits = |outfits: &[(Outfit, u32)]| {
+                outfits
+                    .iter()
+                    .filter_map(|(outfit, count)| {
+                        self.get_outfit(&outfit.name)
+                            .map(|outfit| (outfit.clone(), *count))
+                    })
+                    .collect()
+            };
+            owned.outfits = refresh_outfits(&owned.outfits);
+            owned.cargo.outfits = refresh_outfits(&owned.cargo.outfits);
+        }
+        if let Some(system) = systems
+            .iter()
+            .find(|system| system.name == game.player.current_system.name)
+        {
+            game.player.current_system = system.clone();
+        }
+        if let Some(current_planet) = game.player.current_planet.as_ref() {
+            if let Some(planet) = planets
+                .iter()
+                .find(|planet| planet.name == current_planet.name)
+            {
+                game.player.current_planet = Some(planet.clone());
+            }
+        }
+        game.ships = ships;
+        game.systems = systems;
+        game.outfits = self.outfits.iter().cloned().map(Arc::new).collect();
+        game.planets = planets;
+        game.governments = self.governments.iter().cloned().map(Arc::new).collect();
+        game.galaxies = self.galaxies.iter().cloned().map(Arc::new).collect();
+        game.missions = self.missions.iter().cloned().map(Arc::new).collect();
+    }
+
     /// Create a game from the loaded files
-    pub fn create_game(&self) -> Result<super::Game, ()> {
+    pub fn create_game(&self) -> Result<super::Game, GameCreationError> {
         let mut rng = rand::thread_rng();
 
         if self.ships.is_empty() {
-            return Err(());
+            return Err(GameCreationError::NoShips);
         }
-        let ships: Vec<Arc<Ship>> = self.ships.iter().cloned().map(Arc::new).collect();
+        let ships: Vec<Arc<ShipModel>> = self.ships.iter().cloned().map(Arc::new).collect();
 
         if self.systems.is_empty() {
-            return Err(());
+            return Err(GameCreationError::NoSystems);
         }
         let systems: Vec<Arc<System>> = self.systems.iter().cloned().map(Arc::new).collect();
+        let planets: Vec<Arc<Planet>> = self.planets.iter().cloned().map(Arc::new).collect();
 
-        let (start_system, start_date) = if let Some(start) = self.start.clone() {
-            let (y, m, d) = start.1;
-            (
-                systems
+        let (start_system, start_planet, start_date, start_account, start_fleet) =
+            if let Some(start) = self.start.clone() {
+                let (year, month, day) = start.date;
+                if start.ships.is_empty() {
+                    return Err(GameCreationError::NoStartShip);
+                }
+                (
+                    systems
+                        .iter()
+                        .find(|system| system.name == start.system)
+                        .ok_or_else(|| GameCreationError::UnknownStartSystem(start.system.clone()))?
+                        .clone(),
+                    start.planet.and_then(|name| {
+                        planets.iter().find(|planet| planet.name == name).cloned()
+                    }),
+                    chrono::NaiveDate::from_ymd_opt(year, month, day)
+                        .ok_or(GameCreationError::InvalidStartDate { year, month, day })?,
+                    start.account,
+                    start
+                        .ships
+                        .iter()
+                        .map(|(model, name)| {
+                            ships
+                                .iter()
+                                .find(|ship| &ship.name == model)
+                                .map(|ship| {
+                                    ShipInstance::new(name.as_ref().unwrap_or(model), ship.clone())
+                                })
+                                .ok_or_else(|| GameCreationError::UnknownStartShip(model.clone()))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            } else {
+                let start_ship = ships
                     .iter()
-                    .find(|system| system.name == start.0)
-                    .ok_or(())?
-                    .clone(),
-                chrono::NaiveDate::from_ymd_opt(y, m, d).ok_or(())?,
-            )
-        } else {
-            (
-                systems.iter().choose(&mut rng).ok_or(())?.clone(),
-                chrono::NaiveDate::from_ymd(2020, 1, 1),
-            )
-        };
-        let start_ship = ships.iter().choose(&mut rng).ok_or(())?.clone();
+                    .choose(&mut rng)
+                    .ok_or(GameCreationError::NoShips)?;
+                (
+                    systems
+                        .iter()
+                        .choose(&mut rng)
+                        .ok_or(GameCreationError::NoSystems)?
+                        .clone(),
+                    None,
+                    chrono::NaiveDate::from_ymd(2020, 1, 1),
+                    crate::account::Account::default(),
+                    vec![ShipInstance::new(&start_ship.name, start_ship.clone())],
+                )
+            };
+
+        let mut conditions = super::conditions::ConditionStore::new();
+        conditions.set_date(start_date);
+
+        let mut reputations = crate::politics::Reputations::default();
+        for government in &self.governments {
+            reputations.set(&government.name, government.initial_reputation);
+        }
+
+        let mut exploration = crate::exploration::Exploration::default();
+        exploration.visit_system(&start_system.name);
+        if let Some(planet) = &start_planet {
+            exploration.visit_planet(&planet.name);
+        }
 
         Ok(super::Game {
             current_date: start_date,
+            conditions,
             ships,
             systems,
+            outfits: self.outfits.iter().cloned().map(Arc::new).collect(),
+            planets,
+            governments: self.governments.iter().cloned().map(Arc::new).collect(),
+            galaxies: self.galaxies.iter().cloned().map(Arc::new).collect(),
+            missions: self.missions.iter().cloned().map(Arc::new).collect(),
+            daily_systems: super::daily::default_daily_systems(),
             player: Player {
                 current_system: start_system,
-                ship: start_ship,
+                current_planet: start_planet,
+                fleet: start_fleet,
+                flagship: 0,
+                account: start_account,
+                depreciation: crate::shop::Depreciation::default(),
+                reputations,
+                bribed_planets: vec![],
+                exploration,
+                missions: Default::default(),
+            },
+        })
+    }
+
+    /// Load a saved game, finding the data it references by name
+    pub fn load_game(&self, saved_game: &SavedGame) -> Result<super::Game, SaveError> {
+        if saved_game.version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(saved_game.version));
+        }
+
+        let ships: Vec<Arc<ShipModel>> = self.ships.iter().cloned().map(Arc::new).collect();
+        let systems: Vec<Arc<System>> = self.systems.iter().cloned().map(Arc::new).collect();
+        let planets: Vec<Arc<Planet>> = self.planets.iter().cloned().map(Arc::new).collect();
+
+        let (year, month, day) = saved_game.date;
+        let current_date = chrono::NaiveDate::from_ymd_opt(year, month, day)
+            .ok_or(SaveError::InvalidDate { year, month, day })?;
+
+        let current_system = systems
+            .iter()
+            .find(|system| system.name == saved_game.current_system)
+            .ok_or_else(|| SaveError::UnknownSystem(saved_game.current_system.clone()))?
+            .clone();
+
+        let current_planet = match saved_game.current_planet.as_ref() {
+            Some(name) => Some(
+                planets
+                    .iter()
+                    .find(|planet| &planet.name == name)
+                    .ok_or_else(|| SaveError::UnknownPlanet(name.clone()))?
+                    .clone(),
+            ),
+            None => None,
+        };
+
+        let fleet = saved_game
+            .ships
+            .iter()
+            .map(|ship| self.load_ship(&ships, ship))
+            .collect::<Result<Vec<_>, _>>()?;
+        if saved_game.flagship >= fleet.len() {
+            return Err(SaveError::NoShip);
+        }
+
+        let mut conditions = super::conditions::ConditionStore::new();
+        for (name, value) in &saved_game.conditions {
+            conditions.set(name, *value);
+        }
+
+        Ok(super::Game {
+            current_date,
+            conditions,
+            ships,
+            systems,
+            outfits: self.outfits.iter().cloned().map(Arc::new).collect(),
+            planets,
+            governments: self.governments.iter().cloned().map(Arc::new).collect(),
+            galaxies: self.galaxies.iter().cloned().map(Arc::new).collect(),
+            missions: self.missions.iter().cloned().map(Arc::new).collect(),
+            daily_systems: super::daily::default_daily_systems(),
+            player: Player {
+                current_system,
+                current_planet,
+                fleet,
+                flagship: saved_game.flagship,
+                account: saved_game.account.clone(),
+                depreciation: saved_game.depreciation.clone(),
+                reputations: saved_game.reputations.clone(),
+                bribed_planets: vec![],
+                exploration: saved_game.exploration.clone(),
+                missions: saved_game.missions.clone(),
             },
         })
     }
+
+    /// Load a saved ship, with it's outfits and cargo. It's energy is recharged and it's cooled
+    /// down.
+    fn load_ship(
+        &self,
+        ships: &[Arc<ShipModel>],
+        saved_ship: &SavedShip,
+    ) -> Result<ShipInstance, SaveError> {
+        let model = ships
+            .iter()
+            .find(|ship| ship.name == saved_ship.model)
+            .ok_or_else(|| SaveError::UnknownShip(saved_ship.model.clone()))?;
+        let load_outfits = |outfits: &[(String, u32)]| {
+            outfits
+                .iter()
+                .map(|(name, count)| {
+                    self.get_outfit(name)
+                        .map(|outfit| (outfit.clone(), *count))
+                        .ok_or_else(|| SaveError::UnknownOutfit(name.clone()))
+                })
+                .collect::<Result<_, _>>()
+        };
+        let mut ship = ShipInstance::new(&saved_ship.name, model.clone());
+        ship.outfits = load_outfits(&saved_ship.outfits)?;
+        ship.cargo = crate::cargo::Cargo {
+            commodities: saved_ship.cargo.clone(),
+            mission_cargo: saved_ship.mission_cargo.clone(),
+            outfits: load_outfits(&saved_ship.cargo_outfits)?,
+        };
+        ship.restore();
+        ship.hull = saved_ship.hull;
+        ship.shields = saved_ship.shields;
+        ship.fuel = saved_ship.fuel;
+        ship.parked_on = saved_ship.parked_on.clone();
+        Ok(ship)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OUTFITS: &str = r#"outfit "Little Thruster"
+    category "Engines"
+    "mass" 10
+    "thrust" 4
+"#;
+
+    const SHIPS: &str = r#"ship "Shuttle"
+    sprite "ship/shuttle"
+    thumbnail "thumbnail/shuttle"
+    attributes
+        category "Transport"
+        "cost" 100000
+        "hull" 100
+        "mass" 50
+        "drag" 1
+        "heat dissipation" 1
+        "outfit space" 100
+        "engine capacity" 60
+        weapon
+            "blast radius" 10
+            "shield damage" 100
+            "hull damage" 50
+            "hit force" 200
+    outfits
+        "Little Thruster" 2
+
+    engine 0 50
+    explode "explosion" 10
+    description "My Shuttle."
+"#;
+
+    const SYSTEMS: &str = r#"system Home
+    pos 0 0
+    government Independent
+    habitable 100
+    object
+        sprite star/g0
+        period 10
+"#;
+
+    #[test]
+    fn can_reload_a_file() {
+        let mut loader = UnresolvedESGameLoader::empty();
+        loader.load("outfits.txt", OUTFITS);
+        loader.load("ships.txt", SHIPS);
+        loader.load("map.txt", SYSTEMS);
+
+        let mut game = loader.resolve().create_game().unwrap();
+        assert_eq!(game.player.flagship().get_forward_thrust(), 8.0);
+
+        loader.reload(
+            "outfits.txt",
+            &OUTFITS.replace("\"thrust\" 4", "\"thrust\" 6"),
+        );
+        assert_eq!(
+            loader.files().collect::<Vec<_>>(),
+            vec!["outfits.txt", "ships.txt", "map.txt"]
+        );
+        loader.resolve().refresh_game(&mut game);
+        assert_eq!(game.player.flagship().get_forward_thrust(), 12.0);
+        assert_eq!(game.player.current_system.name, "Home");
+
+        loader.unload("outfits.txt");
+        loader.resolve().refresh_game(&mut game);
+        assert_eq!(game.player.flagship().get_forward_thrust(), 0.0);
+    }
+
+    #[test]
+    fn can_load_galaxies_from_several_files() {
+        let mut loader = UnresolvedESGameLoader::empty();
+        loader.load("ships.txt", SHIPS);
+        loader.load("map.txt", SYSTEMS);
+        loader.load(
+            "galaxies.txt",
+            "galaxy \"Milky Way\"\n\tpos -27 32.8\n\tsprite ui/galaxy\n\n\
+             galaxy \"Ember Waste\"\n\tpos 1000 -500\n",
+        );
+        loader.load(
+            "plugin.txt",
+            "galaxy \"Ember Waste\"\n\tpos 1200 -500\n\tsprite ui/ember\n\n\
+             galaxy Andromeda\n\tpos 5000 5000\n\tsprite ui/andromeda\n",
+        );
+
+        let game = loader.resolve().create_game().unwrap();
+        let galaxies = game
+            .galaxies
+            .iter()
+            .map(|galaxy| {
+                (
+                    galaxy.name.as_str(),
+                    galaxy.position.x,
+                    galaxy.sprite.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            galaxies,
+            vec![
+                ("Milky Way", -27.0, Some("ui/galaxy")),
+                ("Ember Waste", 1200.0, Some("ui/ember")),
+                ("Andromeda", 5000.0, Some("ui/andromeda")),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_save_and_load_a_game() {
+        let mut loader = UnresolvedESGameLoader::empty();
+        loader.load("outfits.txt", OUTFITS);
+        loader.load("ships.txt", SHIPS);
+        loader.load("map.txt", SYSTEMS);
+        let game_data = loader.resolve();
+
+        let mut game = game_data.create_game().unwrap();
+        game.player.account.credits = 1234;
+        game.player.account.add_fine(500);
+        game.conditions.set("visited Sol", 1);
+        game.player.flagship_mut().cargo.add_commodity("Food", 5);
+
+        let content = SavedGame::from_game(&game).to_ron().unwrap();
+        let saved_game = SavedGame::from_ron(&content).unwrap();
+        assert_eq!(saved_game, SavedGame::from_game(&game));
+
+        let loaded = game_data.load_game(&saved_game).unwrap();
+        assert_eq!(loaded.current_date, game.current_date);
+        assert_eq!(loaded.player.account, game.player.account);
+        assert_eq!(loaded.player.flagship().name, game.player.flagship().name);
+        assert_eq!(
+            loaded.player.flagship().get_forward_thrust(),
+            game.player.flagship().get_forward_thrust()
+        );
+        assert_eq!(
+            loaded.player.current_system.name,
+            game.player.current_system.name
+        );
+        assert_eq!(loaded.conditions.get("visited Sol"), 1);
+        assert_eq!(loaded.player.flagship().cargo.get_commodity("Food"), 5);
+
+        let mut unsupported = saved_game.clone();
+        unsupported.version = SAVE_VERSION + 1;
+        assert_eq!(
+            game_data.load_game(&unsupported).unwrap_err(),
+            SaveError::UnsupportedVersion(SAVE_VERSION + 1)
+        );
+
+        let mut unknown_outfit = saved_game;
+        unknown_outfit.ships[0].outfits = vec![(String::from("Warp Drive"), 1)];
+        assert_eq!(
+            game_data.load_game(&unknown_outfit).unwrap_err(),
+            SaveError::UnknownOutfit(String::from("Warp Drive"))
+        );
+    }
+
+    #[test]
+    fn can_import_and_export_es_pilots() {
+        let mut loader = UnresolvedESGameLoader::empty();
+        loader.load("outfits.txt", OUTFITS);
+        loader.load("ships.txt", SHIPS);
+        loader.load("map.txt", SYSTEMS);
+        let game_data = loader.resolve();
+
+        let mut game = game_data.create_game().unwrap();
+        game.player.reputations.set("Republic", 2.5);
+        let exported = SavedGame::from_game(&game).to_es_pilot("Bob", "Smith");
+
+        let pilot = es_data_parser::parse_pilot(&exported).unwrap();
+        assert_eq!((pilot.first_name, pilot.last_name), ("Bob", "Smith"));
+        let imported = game_data
+            .load_game(&SavedGame::from_es_pilot(&pilot).unwrap())
+            .unwrap();
+        assert_eq!(imported.current_date, game.current_date);
+        let (imported_ship, ship) = (imported.player.flagship(), game.player.flagship());
+        assert_eq!(imported_ship.name, ship.name);
+        assert_eq!(imported_ship.get_mass(), ship.get_mass());
+        assert_eq!(imported_ship.hull, ship.hull);
+        assert_eq!(imported_ship.fuel, ship.fuel);
+        assert_eq!(
+            imported.player.current_system.name,
+            game.player.current_system.name
+        );
+        assert_eq!(imported.conditions.get("year"), game.conditions.get("year"));
+        assert_eq!(imported.reputation("Republic"), 2.5);
+    }
+
+    #[test]
+    fn can_report_why_a_game_cant_be_created() {
+        let mut loader = UnresolvedESGameLoader::empty();
+        assert_eq!(
+            loader.resolve().create_game().unwrap_err(),
+            GameCreationError::NoShips
+        );
+
+        loader.load("ships.txt", SHIPS);
+        assert_eq!(
+            loader.resolve().create_game().unwrap_err(),
+            GameCreationError::NoSystems
+        );
+
+        loader.load("map.txt", SYSTEMS);
+        const START: &str = r#"start
+	system "Home"
+	planet "New Boston"
+	date 16 11 3013
+	set "license: Pilot's"
+	account
+		credits 100000
+		score 400
+		mortgage Mortgage
+			principal 480000
+			interest 0.004
+			term 365
+"#;
+        loader.load("start.txt", START);
+        assert_eq!(
+            loader.resolve().create_game().unwrap_err(),
+            GameCreationError::NoStartShip
+        );
+
+        loader.reload(
+            "start.txt",
+            &START.replace("\taccount", "\tship \"Star Barge\"\n\taccount"),
+        );
+        assert_eq!(
+            loader.resolve().create_game().unwrap_err(),
+            GameCreationError::UnknownStartShip(String::from("Star Barge"))
+        );
+
+        loader.reload(
+            "start.txt",
+            &START
+                .replace("\taccount", "\tship \"Shuttle\"\n\taccount")
+                .replace("\"Home\"", "\"Rutilicus\""),
+        );
+        let error = loader.resolve().create_game().unwrap_err();
+        assert_eq!(
+            error,
+            GameCreationError::UnknownStartSystem(String::from("Rutilicus"))
+        );
+        assert_eq!(error.to_string(), "start system 'Rutilicus' not found");
+    }
 }
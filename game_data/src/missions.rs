@@ -0,0 +1,958 @@
+use std::sync::Arc;
+
+use chrono::Datelike;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub use es_data_parser::{MissionDeadline, MissionLocation, MissionPayment, MissionTrigger};
+
+use super::conditions::Condition;
+use super::data::{Planet, System};
+use super::navigation::RouteCriteria;
+use super::{Game, MissionError};
+
+/// credits for each jump and ton of payload paid by a `payment` without values
+pub const DEFAULT_PAYMENT_MULTIPLIER: i64 = 150;
+/// days given by a `deadline` without values
+pub const DEFAULT_DEADLINE_DAYS: u32 = 1;
+/// days added for each jump to the destination by a `deadline` without values
+pub const DEFAULT_DEADLINE_MULTIPLIER: u32 = 2;
+
+/// Filter on planets, by their name, system, government or attributes. An empty list allows
+/// everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanetFilter {
+    /// names of the planets allowed
+    pub planets: Vec<String>,
+    /// names of the systems allowed
+    pub systems: Vec<String>,
+    /// names of the governments allowed
+    pub governments: Vec<String>,
+    /// attributes the planet must have, at least one of each list
+    pub attributes: Vec<Vec<String>>,
+}
+
+impl PlanetFilter {
+    /// create a filter from ES data, a planet given by it's name being the only one allowed
+    pub fn from_es_place(place: &es_data_parser::MissionPlace) -> Self {
+        let names = |names: &[&str]| names.iter().map(|name| String::from(*name)).collect();
+        match place {
+            es_data_parser::MissionPlace::Planet(name) => PlanetFilter {
+                planets: vec![String::from(*name)],
+                ..Default::default()
+            },
+            es_data_parser::MissionPlace::Filter(filter) => PlanetFilter {
+                planets: names(&filter.planets),
+                systems: names(&filter.systems),
+                governments: names(&filter.governments),
+                attributes: filter
+                    .attributes
+                    .iter()
+                    .map(|attributes| names(attributes))
+                    .collect(),
+            },
+        }
+    }
+
+    /// does a planet of a system match the filter
+    pub fn matches(&self, planet: &Planet, system: &System) -> bool {
+        let government = planet.government.as_ref().unwrap_or(&system.government);
+        (self.planets.is_empty() || self.planets.contains(&planet.name))
+            && (self.systems.is_empty() || self.systems.contains(&system.name))
+            && (self.governments.is_empty() || self.governments.contains(government))
+            && self.attributes.iter().all(|attributes| {
+                attributes
+                    .iter()
+                    .any(|attribute| planet.has_attribute(attribute))
+            })
+    }
+}
+
+/// Actions of a mission, run when something happens to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissionAction {
+    /// when they are run
+    pub trigger: MissionTrigger,
+    /// credits paid
+    pub payment: Option<MissionPayment>,
+    /// changes to the player conditions
+    pub conditions: Vec<Condition>,
+    /// outfits put in the flagship cargo, or taken from it when the count is negative
+    pub outfits: Vec<(String, i32)>,
+    /// events triggered, with their delay in days
+    pub events: Vec<(String, u32)>,
+}
+
+/// A mission, that can be offered to the player on planets
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mission {
+    /// it's identifier, used in the player conditions
+    pub id: String,
+    /// name shown to the player
+    pub name: String,
+    /// description shown to the player
+    pub description: String,
+    /// where it's offered
+    pub location: MissionLocation,
+    /// number of times it can be offered, unlimited if `None`
+    pub max_offers: Option<u32>,
+    /// days to complete it once accepted, if it has a deadline
+    pub deadline: Option<MissionDeadline>,
+    /// cargo to carry, with it's quantity in tons
+    pub cargo: Option<(String, u32)>,
+    /// passengers to carry
+    pub passengers: u32,
+    /// planets where it can be offered, anywhere if `None`
+    pub source: Option<PlanetFilter>,
+    /// planets one of which will be where it must be completed, the planet where it was
+    /// offered if `None`
+    pub destination: Option<PlanetFilter>,
+    /// conditions to offer it, `random` being a number between 0 and 99
+    pub to_offer: Vec<Condition>,
+    /// conditions to complete it
+    pub to_complete: Vec<Condition>,
+    /// conditions that make it fail
+    pub to_fail: Vec<Condition>,
+    /// it's actions
+    pub actions: Vec<MissionAction>,
+}
+
+impl Mission {
+    /// create a mission from ES data
+    pub fn from_es_mission(mission: &es_data_parser::Mission) -> Self {
+        let conditions = |conditions: &[es_data_parser::Condition]| {
+            conditions
+                .iter()
+                .map(Condition::from_es_condition)
+                .collect()
+        };
+        Mission {
+            id: String::from(mission.id),
+            name: String::from(mission.name.unwrap_or(mission.id)),
+            description: mission.description.map(String::from).unwrap_or_default(),
+            location: mission.location,
+            max_offers: match mission.repeat {
+                None => Some(1),
+                Some(repeat) => repeat,
+            },
+            deadline: mission.deadline,
+            cargo: mission
+                .cargo
+                .map(|cargo| (String::from(cargo.kind), cargo.tons)),
+            passengers: mission.passengers.unwrap_or(0),
+            source: mission.source.as_ref().map(PlanetFilter::from_es_place),
+            destination: mission
+                .destination
+                .as_ref()
+                .map(PlanetFilter::from_es_place),
+            to_offer: conditions(&mission.to_offer),
+            to_complete: conditions(&mission.to_complete),
+            to_fail: conditions(&mission.to_fail),
+            actions: mission
+                .actions
+                .iter()
+                .map(|action| MissionAction {
+                    trigger: action.trigger,
+                    payment: action.payment,
+                    conditions: conditions(&action.conditions),
+                    outfits: action
+                        .outfits
+                        .iter()
+                        .map(|(outfit, count)| (String::from(*outfit), *count))
+                        .collect(),
+                    events: action
+                        .events
+                        .iter()
+                        .map(|(event, delay)| (String::from(*event), delay.unwrap_or(0)))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// actions run on a trigger
+    pub fn actions(&self, trigger: MissionTrigger) -> impl Iterator<Item = &MissionAction> {
+        self.actions
+            .iter()
+            .filter(move |action| action.trigger == trigger)
+    }
+
+    /// name of one of it's conditions, like `offered` or `done`
+    pub fn condition(&self, suffix: &str) -> String {
+        format!("{}: {}", self.id, suffix)
+    }
+}
+
+/// credits paid for a mission carrying a payload a number of jumps away, the payload being the
+/// tons of cargo plus 10 for each passenger
+pub fn payment_value(payment: MissionPayment, jumps: u32, payload: u32) -> i64 {
+    let (base, multiplier) = match (payment.base, payment.multiplier) {
+        (None, None) => (0, DEFAULT_PAYMENT_MULTIPLIER),
+        (base, multiplier) => (base.unwrap_or(0), multiplier.unwrap_or(0)),
+    };
+    base + (i64::from(jumps) + 1) * i64::from(payload) * multiplier
+}
+
+/// days given to complete a mission going a number of jumps away
+pub fn deadline_days(deadline: MissionDeadline, jumps: u32) -> u32 {
+    let (days, multiplier) = match (deadline.days, deadline.multiplier) {
+        (None, None) => (DEFAULT_DEADLINE_DAYS, DEFAULT_DEADLINE_MULTIPLIER),
+        (days, multiplier) => (days.unwrap_or(0), multiplier.unwrap_or(0)),
+    };
+    days + multiplier * jumps
+}
+
+/// A mission offered to or accepted by the player, with it's destination chosen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveMission {
+    /// identifier of the mission
+    pub mission: String,
+    /// name shown to the player
+    pub name: String,
+    /// planet where it was offered
+    pub source: String,
+    /// planet where it must be completed
+    pub destination: String,
+    /// system of the destination
+    pub destination_system: String,
+    /// jumps from the source to the destination
+    pub jumps: u32,
+    /// last day to complete it, as year, month and day
+    pub deadline: Option<(i32, u32, u32)>,
+    /// cargo carried, with it's quantity in tons
+    pub cargo: Option<(String, u32)>,
+    /// passengers carried
+    pub passengers: u32,
+    /// credits paid on completion
+    pub payment: i64,
+}
+
+impl ActiveMission {
+    /// tons of cargo plus 10 for each passenger, used to compute the payment
+    pub fn payload(&self) -> u32 {
+        self.cargo.as_ref().map(|(_, tons)| *tons).unwrap_or(0) + 10 * self.passengers
+    }
+
+    /// last day to complete it
+    pub fn deadline_date(&self) -> Option<chrono::NaiveDate> {
+        self.deadline
+            .and_then(|(year, month, day)| chrono::NaiveDate::from_ymd_opt(year, month, day))
+    }
+}
+
+/// Missions of the player
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Missions {
+    active: Vec<ActiveMission>,
+    /// offers are made again each time the player lands
+    #[serde(skip)]
+    offers: Vec<ActiveMission>,
+    /// events waiting to happen, with their date as year, month and day
+    events: Vec<(String, (i32, u32, u32))>,
+}
+
+impl Missions {
+    /// missions accepted and not yet completed or failed
+    pub fn active(&self) -> &[ActiveMission] {
+        &self.active
+    }
+
+    /// missions offered on the planet the player is landed on
+    pub fn offers(&self) -> &[ActiveMission] {
+        &self.offers
+    }
+
+    /// is a mission accepted and not yet completed or failed
+    pub fn is_active(&self, mission: &str) -> bool {
+        self.active.iter().any(|active| active.mission == mission)
+    }
+
+    /// passengers carried for the active missions
+    pub fn passengers(&self) -> u32 {
+        self.active.iter().map(|active| active.passengers).sum()
+    }
+
+    /// remove the offers, when the player leaves the planet
+    pub fn clear_offers(&mut self) {
+        self.offers.clear();
+    }
+
+    /// copy of the missions to save, without the offers that are made again on landing
+    pub fn to_save(&self) -> Self {
+        Missions {
+            offers: vec![],
+            ..self.clone()
+        }
+    }
+}
+
+/// What happened to a mission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissionOutcome {
+    /// it was completed and it's rewards given
+    Completed,
+    /// it failed
+    Failed,
+    /// it's destination was visited before it could be completed
+    Visited,
+}
+
+/// Something that happened to an active mission
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissionUpdate {
+    /// name of the mission
+    pub name: String,
+    /// what happened
+    pub outcome: MissionOutcome,
+}
+
+impl Game {
+    /// Find a mission by it's identifier
+    pub fn get_mission(&self, id: &str) -> Option<&Arc<Mission>> {
+        self.missions.iter().find(|mission| mission.id == id)
+    }
+
+    /// Find the system a planet is in
+    pub fn planet_system(&self, planet: &str) -> Option<&Arc<System>> {
+        self.systems
+            .iter()
+            .find(|system| system.planets().contains(&planet))
+    }
+
+    /// missions offered where the player is landed
+    pub fn mission_offers(&self, location: MissionLocation) -> Vec<&ActiveMission> {
+        self.player
+            .missions
+            .offers
+            .iter()
+            .filter(|offer| {
+                self.get_mission(&offer.mission)
+                    .map(|mission| mission.location == location)
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Make the offers of the planet the player is landed on, replacing the previous ones. A
+    /// mission is offered if it can be offered again, it's source matches the planet, it's
+    /// conditions are true and a destination can be reached. Only the missions offered on landing
+    /// are made on planets without a spaceport. Jobs are listed without being offered, until one
+    /// is accepted.
+    pub fn generate_mission_offers<R: Rng>(&mut self, rng: &mut R) {
+        self.player.missions.offers.clear();
+        let planet = match self.player.current_planet.clone() {
+            Some(planet) => planet,
+            None => return,
+        };
+        for mission in self.missions.clone() {
+            if self.player.missions.is_active(&mission.id) {
+                continue;
+            }
+            if mission.location != MissionLocation::Landing && !planet.has_spaceport() {
+                continue;
+            }
+            if let Some(max_offers) = mission.max_offers {
+                if self.conditions.get(&mission.condition("offered")) >= i64::from(max_offers) {
+                    continue;
+                }
+            }
+            if let Some(source) = &mission.source {
+                if !source.matches(&planet, &self.player.current_system) {
+                    continue;
+                }
+            }
+            let mut conditions = self.conditions.clone();
+            conditions.set("random", rng.gen_range(0, 100));
+            if !conditions.test_all(&mission.to_offer) {
+                continue;
+            }
+            if let Some(offer) = self.instantiate_mission(&mission, &planet, rng) {
+                if mission.location != MissionLocation::Job {
+                    self.offer_mission(&mission, &offer);
+                }
+                self.player.missions.offers.push(offer);
+            }
+        }
+    }
+
+    fn offer_mission(&mut self, mission: &Mission, offer: &ActiveMission) {
+        self.conditions
+            .apply(&Condition::Increment(mission.condition("offered")));
+        self.run_mission_actions(mission, offer, MissionTrigger::Offer);
+    }
+
+    /// Choose the destination of a mission offered on a planet, and compute it's deadline and
+    /// payment
+    fn instantiate_mission<R: Rng>(
+        &self,
+        mission: &Mission,
+        source: &Planet,
+        rng: &mut R,
+    ) -> Option<ActiveMission> {
+        let (destination, system) = match &mission.destination {
+            None => (source.name.clone(), self.player.current_system.clone()),
+            Some(filter) => self
+                .planets
+                .iter()
+                .filter(|planet| planet.name != source.name)
+                .filter_map(|planet| {
+                    self.planet_system(&planet.name)
+                        .filter(|system| filter.matches(planet, system))
+                        .map(|system| (planet.name.clone(), system.clone()))
+                })
+                .choose(rng)?,
+        };
+        let jumps = if system.name == self.player.current_system.name {
+            0
+        } else {
+            self.plan_route(&system.name, RouteCriteria::Jumps)?.jumps() as u32
+        };
+        let deadline = mission.deadline.map(|deadline| {
+            let date = self.current_date
+                + chrono::Duration::days(i64::from(deadline_days(deadline, jumps)));
+            (date.year(), date.month(), date.day())
+        });
+        let mut offer = ActiveMission {
+            mission: mission.id.clone(),
+            name: mission.name.clone(),
+            source: source.name.clone(),
+            destination,
+            destination_system: system.name.clone(),
+            jumps,
+            deadline,
+            cargo: mission.cargo.clone(),
+            passengers: mission.passengers,
+            payment: 0,
+        };
+        offer.payment = mission
+            .actions(MissionTrigger::Complete)
+            .filter_map(|action| action.payment)
+            .map(|payment| payment_value(payment, jumps, offer.payload()))
+            .sum();
+        Some(offer)
+    }
+
+    /// Run the actions of a mission for a trigger: pay the credits, change the conditions,
+    /// give or take outfits and trigger events
+    fn run_mission_actions(
+        &mut self,
+        mission: &Mission,
+        active: &ActiveMission,
+        trigger: MissionTrigger,
+    ) {
+        for action in mission.actions(trigger) {
+            if let Some(payment) = action.payment {
+                self.player.account.credits +=
+                    payment_value(payment, active.jumps, active.payload());
+            }
+            self.conditions.apply_all(&action.conditions);
+            for (name, count) in &action.outfits {
+                if *count > 0 {
+                    if let Some(outfit) = self.outfits.iter().find(|outfit| &outfit.name == name) {
+                        self.player
+                            .flagship_mut()
+                            .cargo
+                            .add_outfit(outfit, *count as u32);
+                    }
+                } else {
+                    self.player
+                        .flagship_mut()
+                        .cargo
+                        .remove_outfit(name, count.unsigned_abs());
+                }
+            }
+            for (event, delay) in &action.events {
+                if *delay == 0 {
+                    self.trigger_event(event);
+                } else {
+                    let date = self.current_date + chrono::Duration::days(i64::from(*delay));
+                    self.player
+                        .missions
+                        .events
+                        .push((event.clone(), (date.year(), date.month(), date.day())));
+                }
+            }
+        }
+    }
+
+    /// Trigger an event, which sets the condition `event: <name>`
+    pub fn trigger_event(&mut self, event: &str) {
+        self.conditions.set(&format!("event: {}", event), 1);
+    }
+
+    /// Trigger the events that were waiting for the current date
+    pub fn trigger_due_events(&mut self) {
+        let current_date = self.current_date;
+        let (due, waiting) = self
+            .player
+            .missions
+            .events
+            .drain(..)
+            .partition::<Vec<_>, _>(|(_, (year, month, day))| {
+                chrono::NaiveDate::from_ymd_opt(*year, *month, *day)
+                    .map(|date| date <= current_date)
+                    .unwrap_or(true)
+            });
+        self.player.missions.events = waiting;
+        for (event, _) in due {
+            self.trigger_event(&event);
+        }
+    }
+
+    /// Accept a mission offered on the current planet, loading it's cargo and passengers. A job
+    /// is offered when it is accepted.
+    pub fn accept_mission(&mut self, id: &str) -> Result<(), MissionError> {
+        let index = self
+            .player
+            .missions
+            .offers
+            .iter()
+            .position(|offer| offer.mission == id)
+            .ok_or_else(|| MissionError::NotOffered(String::from(id)))?;
+        let offer = self.player.missions.offers[index].clone();
+        let tons = offer.cargo.as_ref().map(|(_, tons)| *tons).unwrap_or(0);
+        let available = self.player.free_cargo_space();
+        if tons > available {
+            return Err(MissionError::NotEnoughCargoSpace {
+                needed: tons,
+                available,
+            });
+        }
+        let available = self.player.free_bunks();
+        if offer.passengers > available {
+            return Err(MissionError::NotEnoughBunks {
+                needed: offer.passengers,
+                available,
+            });
+        }
+
+        if tons > 0 {
+            self.player.add_mission_cargo(&offer.mission, tons);
+        }
+        self.player.missions.offers.remove(index);
+        self.player.missions.active.push(offer.clone());
+        if let Some(mission) = self.get_mission(id).cloned() {
+            if mission.location == MissionLocation::Job {
+                self.offer_mission(&mission, &offer);
+            }
+            self.conditions.set(&mission.condition("active"), 1);
+            self.run_mission_actions(&mission, &offer, MissionTrigger::Accept);
+        }
+        Ok(())
+    }
+
+    /// Decline a mission offered on the current planet
+    pub fn decline_mission(&mut self, id: &str) -> Result<(), MissionError> {
+        let index = self
+            .player
+            .missions
+            .offers
+            .iter()
+            .position(|offer| offer.mission == id)
+            .ok_or_else(|| MissionError::NotOffered(String::from(id)))?;
+        let offer = self.player.missions.offers.remove(index);
+        if let Some(mission) = self.get_mission(id).cloned() {
+            self.conditions
+                .apply(&Condition::Increment(mission.condition("declined")));
+            self.run_mission_actions(&mission, &offer, MissionTrigger::Decline);
+        }
+        Ok(())
+    }
+
+    /// Abort an active mission, which fails it
+    pub fn abort_mission(&mut self, id: &str) -> Result<(), MissionError> {
+        let index = self
+            .player
+            .missions
+            .active
+            .iter()
+            .position(|active| active.mission == id)
+            .ok_or_else(|| MissionError::NotActive(String::from(id)))?;
+        self.end_mission(index, MissionOutcome::Failed);
+        Ok(())
+    }
+
+    /// Remove an active mission with it's cargo, and run it's actions for how it ended
+    fn end_mission(&mut self, index: usize, outcome: MissionOutcome) -> MissionUpdate {
+        let active = self.player.missions.active.remove(index);
+        self.player.remove_mission_cargo(&active.mission);
+        if let Some(mission) = self.get_mission(&active.mission).cloned() {
+            self.conditions.clear(&mission.condition("active"));
+            let (suffix, trigger) = match outcome {
+                MissionOutcome::Completed => ("done", MissionTrigger::Complete),
+                _ => ("failed", MissionTrigger::Fail),
+            };
+            self.conditions
+                .apply(&Condition::Increment(mission.condition(suffix)));
+            self.run_mission_actions(&mission, &active, trigger);
+        }
+        MissionUpdate {
+            name: active.name,
+            outcome,
+        }
+    }
+
+    /// can an active mission be completed on the planet the player is landed on
+    fn can_complete(&self, mission: &Mission, active: &ActiveMission) -> bool {
+        let landed_on_destination = self
+            .player
+            .current_planet
+            .as_ref()
+            .map(|planet| planet.name == active.destination)
+            .unwrap_or(false);
+        let on_time = active
+            .deadline_date()
+            .map(|deadline| self.current_date <= deadline)
+            .unwrap_or(true);
+        let tons = active.cargo.as_ref().map(|(_, tons)| *tons).unwrap_or(0);
+        landed_on_destination
+            && on_time
+            && self.player.get_mission_cargo(&active.mission) >= tons
+            && self.conditions.test_all(&mission.to_complete)
+    }
+
+    /// Check the active missions when landing: they fail if their fail conditions are true,
+    /// and are completed on their destination if possible or else visited
+    pub fn update_missions_on_landing(&mut self) -> Vec<MissionUpdate> {
+        let landed_on = match self.player.current_planet.as_ref() {
+            Some(planet) => planet.name.clone(),
+            None => return vec![],
+        };
+        let mut updates = vec![];
+        let mut index = 0;
+        while index < self.player.missions.active.len() {
+            let active = self.player.missions.active[index].clone();
+            let mission = match self.get_mission(&active.mission).cloned() {
+                Some(mission) => mission,
+                None => {
+                    index += 1;
+                    continue;
+                }
+            };
+            if !mission.to_fail.is_empty() && self.conditions.test_all(&mission.to_fail) {
+                updates.push(self.end_mission(index, MissionOutcome::Failed));
+            } else if self.can_complete(&mission, &active) {
+                updates.push(self.end_mission(index, MissionOutcome::Completed));
+            } else {
+                if active.destination == landed_on {
+                    self.run_mission_actions(&mission, &active, MissionTrigger::Visit);
+                    updates.push(MissionUpdate {
+                        name: active.name.clone(),
+                        outcome: MissionOutcome::Visited,
+                    });
+                }
+                index += 1;
+            }
+        }
+        updates
+    }
+
+    /// Fail the active missions whose deadline passed, returning messages for the log
+    pub fn fail_late_missions(&mut self) -> Vec<String> {
+        let mut messages = vec![];
+        let mut index = 0;
+        while index < self.player.missions.active.len() {
+            let late = self.player.missions.active[index]
+                .deadline_date()
+                .map(|deadline| self.current_date > deadline)
+                .unwrap_or(false);
+            if late {
+                let update = self.end_mission(index, MissionOutcome::Failed);
+                messages.push(format!("mission '{}' failed: too late", update.name));
+            } else {
+                index += 1;
+            }
+        }
+        messages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::mock::StepRng;
+
+    use super::*;
+    use crate::save::SavedGame;
+    use crate::test_data;
+    use crate::ESGameLoader;
+
+    const HOME: &str = "\tlink Neighbour\n\tobject \"Home\"\n\t\tperiod 10\n";
+
+    const DATA: &str = r#"outfit "Map"
+	category "Special"
+	"mass" 1
+
+planet "Station"
+	attributes urban
+	description "A station."
+
+planet "Outpost"
+	attributes urban
+	description "An outpost."
+
+system Neighbour
+	pos 200 0
+	government Republic
+	habitable 100
+	link Home
+	link Far
+	object "Station"
+		period 10
+
+system Far
+	pos 400 0
+	government Pirate
+	habitable 100
+	link Neighbour
+	object "Outpost"
+		period 10
+
+mission "Food Delivery"
+	name "Food to the station"
+	job
+	repeat
+	deadline 3 1
+	cargo "Food" 15
+	passengers 1
+	source
+		system "Home"
+	destination
+		government "Republic"
+		attributes "urban"
+	to offer
+		random < 50
+	on offer
+		"food offers" ++
+	on accept
+		"food missions" ++
+	on complete
+		payment 1000 100
+		outfit "Map"
+		event "well fed" 2
+
+mission "Mail"
+	source "Home"
+	destination "Station"
+	cargo "Mail" 1
+	to offer
+		not "banned from the post"
+	to complete
+		has "signed"
+	on visit
+		set "visited without signature"
+	on complete
+		payment
+
+mission "Never"
+	to offer
+		never
+
+mission "Station Job"
+	job
+	source "Station"
+	destination "Home"
+
+mission "Welcome"
+	landing
+	source "Station"
+
+"#;
+
+    fn game_data() -> ESGameLoader {
+        let system = format!("{}{}", test_data::SYSTEM, HOME);
+        test_data::data_from(&[
+            test_data::SHIPS,
+            test_data::PLANET,
+            &system,
+            test_data::START,
+            DATA,
+        ])
+    }
+
+    fn game() -> Game {
+        let mut game = game_data().create_game().unwrap();
+        game.generate_mission_offers(&mut StepRng::new(0, 0));
+        game
+    }
+
+    fn fly_to(game: &mut Game, system: &str, planet: &str) -> Vec<MissionUpdate> {
+        game.take_off();
+        game.jump_to(system).unwrap();
+        game.land(planet, &mut StepRng::new(0, 0)).unwrap().missions
+    }
+
+    #[test]
+    fn can_offer_missions_matching_their_source_and_conditions() {
+        let game = game();
+        let jobs = game.mission_offers(MissionLocation::Job);
+        assert_eq!(jobs.len(), 1);
+        let food = jobs[0];
+        assert_eq!(food.name, "Food to the station");
+        assert_eq!(food.destination, "Station");
+        assert_eq!(food.destination_system, "Neighbour");
+        assert_eq!(food.jumps, 1);
+        assert_eq!(food.deadline, Some((3013, 11, 20)));
+        assert_eq!(food.cargo, Some((String::from("Food"), 15)));
+        // 1000 credits, plus 100 for each jump and ton of the 15 tons and 1 passenger payload
+        assert_eq!(food.payment, 6000);
+        // jobs are only offered once accepted
+        assert_eq!(game.conditions.get("Food Delivery: offered"), 0);
+        assert_eq!(game.conditions.get("food offers"), 0);
+
+        let spaceport = game.mission_offers(MissionLocation::Spaceport);
+        assert_eq!(spaceport.len(), 1);
+        assert_eq!(spaceport[0].name, "Mail");
+        assert_eq!(spaceport[0].payment, 300);
+        assert_eq!(game.conditions.get("Mail: offered"), 1);
+        assert!(game.get_mission("Never").is_some());
+
+        // this rng draws 99 as the random number, too high to offer the food delivery
+        let mut game = game;
+        game.generate_mission_offers(&mut StepRng::new(99 * (u64::MAX / 100 + 1), 0));
+        assert_eq!(game.mission_offers(MissionLocation::Job).len(), 0);
+    }
+
+    #[test]
+    fn can_offer_only_landing_missions_without_spaceport() {
+        let mut game = game();
+        fly_to(&mut game, "Neighbour", "Station");
+        assert!(game.mission_offers(MissionLocation::Job).is_empty());
+        let landing = game.mission_offers(MissionLocation::Landing);
+        assert_eq!(landing.len(), 1);
+        assert_eq!(landing[0].mission, "Welcome");
+    }
+
+    #[test]
+    fn can_accept_missions_with_enough_space() {
+        let mut game = game();
+        game.player.flagship_mut().cargo.add_commodity("Metal", 10);
+        assert_eq!(
+            game.accept_mission("Food Delivery"),
+            Err(MissionError::NotEnoughCargoSpace {
+                needed: 15,
+                available: 10,
+            })
+        );
+        game.player
+            .flagship_mut()
+            .cargo
+            .remove_commodity("Metal", 10);
+
+        game.player.fleet[0].model = Arc::new(crate::data::ShipModel {
+            attributes: crate::data::Attributes {
+                bunks: 1.0,
+                ..game.player.fleet[0].model.attributes
+            },
+            ..(*game.player.fleet[0].model).clone()
+        });
+        assert_eq!(
+            game.accept_mission("Food Delivery"),
+            Err(MissionError::NotEnoughBunks {
+                needed: 1,
+                available: 0,
+            })
+        );
+        game.player.fleet[0].model = game.ships[0].clone();
+
+        assert_eq!(game.accept_mission("Food Delivery"), Ok(()));
+        assert_eq!(game.player.get_mission_cargo("Food Delivery"), 15);
+        assert_eq!(game.player.free_bunks(), 0);
+        assert_eq!(game.conditions.get("Food Delivery: active"), 1);
+        assert_eq!(game.conditions.get("food missions"), 1);
+        assert_eq!(game.conditions.get("Food Delivery: offered"), 1);
+        assert_eq!(game.conditions.get("food offers"), 1);
+        assert!(game.mission_offers(MissionLocation::Job).is_empty());
+        assert_eq!(
+            game.accept_mission("Food Delivery"),
+            Err(MissionError::NotOffered(String::from("Food Delivery")))
+        );
+
+        assert_eq!(game.decline_mission("Mail"), Ok(()));
+        assert_eq!(game.conditions.get("Mail: declined"), 1);
+        game.generate_mission_offers(&mut StepRng::new(0, 0));
+        assert!(game.mission_offers(MissionLocation::Spaceport).is_empty());
+    }
+
+    #[test]
+    fn can_complete_missions_on_their_destination() {
+        let mut game = game();
+        game.accept_mission("Food Delivery").unwrap();
+        let updates = fly_to(&mut game, "Neighbour", "Station");
+        assert_eq!(
+            updates,
+            vec![MissionUpdate {
+                name: String::from("Food to the station"),
+                outcome: MissionOutcome::Completed,
+            }]
+        );
+        assert_eq!(game.player.account.credits, 16_000);
+        assert_eq!(game.player.get_mission_cargo("Food Delivery"), 0);
+        assert_eq!(game.player.flagship().cargo.get_outfit("Map"), 1);
+        assert!(game.player.missions.active().is_empty());
+        assert_eq!(game.conditions.get("Food Delivery: done"), 1);
+        assert_eq!(game.conditions.get("Food Delivery: active"), 0);
+
+        assert_eq!(game.conditions.get("event: well fed"), 0);
+        game.advance_days(2);
+        assert_eq!(game.conditions.get("event: well fed"), 1);
+    }
+
+    #[test]
+    fn can_visit_destination_before_completing() {
+        let mut game = game();
+        game.accept_mission("Mail").unwrap();
+        let updates = fly_to(&mut game, "Neighbour", "Station");
+        assert_eq!(updates[0].outcome, MissionOutcome::Visited);
+        assert_eq!(game.conditions.get("visited without signature"), 1);
+        assert_eq!(game.player.missions.active().len(), 1);
+
+        game.conditions.set("signed", 1);
+        game.take_off();
+        let updates = game
+            .land("Station", &mut StepRng::new(0, 0))
+            .unwrap()
+            .missions;
+        assert_eq!(updates[0].outcome, MissionOutcome::Completed);
+        assert_eq!(game.player.account.credits, 10_300);
+    }
+
+    #[test]
+    fn can_fail_missions() {
+        let mut game = game();
+        game.accept_mission("Food Delivery").unwrap();
+        assert!(game.fail_late_missions().is_empty());
+        let log = game.advance_days(5);
+        assert!(log
+            .iter()
+            .any(|entry| entry.message == "mission 'Food to the station' failed: too late"));
+        assert!(game.player.missions.active().is_empty());
+        assert_eq!(game.player.get_mission_cargo("Food Delivery"), 0);
+        assert_eq!(game.conditions.get("Food Delivery: failed"), 1);
+
+        game.accept_mission("Mail").unwrap();
+        assert_eq!(game.abort_mission("Mail"), Ok(()));
+        assert_eq!(game.conditions.get("Mail: failed"), 1);
+        assert_eq!(
+            game.abort_mission("Mail"),
+            Err(MissionError::NotActive(String::from("Mail")))
+        );
+    }
+
+    #[test]
+    fn can_save_and_load_active_missions() {
+        let game_data = game_data();
+        let mut game = game_data.create_game().unwrap();
+        game.generate_mission_offers(&mut StepRng::new(0, 0));
+        game.accept_mission("Food Delivery").unwrap();
+
+        let saved = SavedGame::from_game(&game);
+        let loaded = game_data.load_game(&saved).unwrap();
+        assert_eq!(
+            loaded.player.missions.active(),
+            game.player.missions.active()
+        );
+        assert_eq!(loaded.player.get_mission_cargo("Food Delivery"), 15);
+        assert!(loaded.player.missions.offers().is_empty());
+    }
+}
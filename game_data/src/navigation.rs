@@ -0,0 +1,589 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+
+use euclid::{UnknownUnit, Vector2D};
+
+use helpers::{max, min};
+
+use super::daily::LogEntry;
+use super::data::{Planet, ShipInstance, System};
+use super::{Game, JumpError};
+
+/// What a route should minimise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RouteCriteria {
+    /// the number of jumps
+    Jumps,
+    /// the fuel used
+    Fuel,
+}
+
+/// A jump of a route
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteStep {
+    /// name of the system reached by the jump
+    pub system: String,
+    /// fuel used by the jump
+    pub fuel: f32,
+    /// if the ship needs to refuel before this jump
+    pub refuel: bool,
+}
+
+/// A route between two systems
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// name of the system the route starts from
+    pub origin: String,
+    /// the jumps to make, in order
+    pub steps: Vec<RouteStep>,
+    /// total fuel used by the jumps
+    pub fuel: f32,
+}
+
+impl Route {
+    /// number of jumps of the route
+    pub fn jumps(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// systems where the ship needs to refuel before jumping
+    pub fn refuel_stops(&self) -> Vec<&str> {
+        let mut previous = self.origin.as_str();
+        let mut stops = vec![];
+        for step in &self.steps {
+            if step.refuel {
+                stops.push(previous);
+            }
+            previous = step.system.as_str();
+        }
+        stops
+    }
+}
+
+/// The systems a ship can jump between, with the fuel needed for each jump
+#[derive(Debug, Clone)]
+pub struct NavigationGraph {
+    systems: Vec<String>,
+    has_spaceport: Vec<bool>,
+    jumps: Vec<Vec<(usize, f32)>>,
+    fuel_capacity: f32,
+    fuel_regeneration: f32,
+}
+
+/// A system to explore when searching for a route, ordered so that the cheapest is explored first
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    cost: (f32, f32),
+    system: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl NavigationGraph {
+    /// Build the graph of jumps a ship can make with it's drives, knowing where it can refuel
+    pub fn new(systems: &[Arc<System>], planets: &[Arc<Planet>], ship: &ShipInstance) -> Self {
+        let fuel_capacity = ship.get_fuel_capacity();
+        let indexes: HashMap<&str, usize> = systems
+            .iter()
+            .enumerate()
+            .map(|(index, system)| (system.name.as_str(), index))
+            .collect();
+        let has_jump_drive = ship.get_jump_drive_fuel().is_some();
+
+        let jumps = systems
+            .iter()
+            .map(|from| {
+                let mut reachable: Vec<usize> = from
+                    .links
+                    .iter()
+                    .filter_map(|link| indexes.get(link.as_str()).copied())
+                    .collect();
+                if has_jump_drive {
+                    reachable.extend(0..systems.len());
+                    reachable.sort_unstable();
+                    reachable.dedup();
+                }
+                reachable
+                    .into_iter()
+                    .filter_map(|to| {
+                        ship.get_jump_fuel(from, &systems[to])
+                            .map(|fuel| (to, fuel))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let has_spaceport = systems
+            .iter()
+            .map(|system| {
+                system.planets().into_iter().any(|name| {
+                    planets
+                        .iter()
+                        .any(|planet| planet.name == name && planet.has_spaceport())
+                })
+            })
+            .collect();
+
+        NavigationGraph {
+            systems: systems.iter().map(|system| system.name.clone()).collect(),
+            has_spaceport,
+            jumps,
+            fuel_capacity,
+            fuel_regeneration: ship.get_fuel_regeneration_per_jump(),
+        }
+    }
+
+    /// Systems that can be reached in one jump from a system, with the fuel needed
+    pub fn neighbours(&self, system: &str) -> Vec<(&str, f32)> {
+        self.index_of(system)
+            .map(|index| {
+                self.jumps[index]
+                    .iter()
+                    .map(|(to, fuel)| (self.systems[*to].as_str(), *fuel))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Find the best route between two systems, starting with the given fuel. Jumps needing more
+    /// fuel than the tank holds are left out. Fuel is collected by ramscoops before each jump
+    /// after the first one, and the route says where the ship needs to fill it's tank to
+    /// continue, which it can only do in systems with a spaceport. There is no route if it runs
+    /// out of fuel elsewhere.
+    pub fn find_route(
+        &self,
+        from: &str,
+        to: &str,
+        criteria: RouteCriteria,
+        fuel: f32,
+    ) -> Option<Route> {
+        let origin = String::from(from);
+        let from = self.index_of(from)?;
+        let to = self.index_of(to)?;
+
+        let mut best_costs: Vec<Option<(f32, f32)>> = vec![None; self.systems.len()];
+        let mut previous: Vec<Option<(usize, f32)>> = vec![None; self.systems.len()];
+        let mut candidates = BinaryHeap::new();
+        best_costs[from] = Some((0.0, 0.0));
+        candidates.push(Candidate {
+            cost: (0.0, 0.0),
+            system: from,
+        });
+
+        while let Some(Candidate { cost, system }) = candidates.pop() {
+            if system == to {
+                break;
+            }
+            if let Some(best) = best_costs[system] {
+                if best < cost {
+                    continue;
+                }
+            }
+            for (next, jump_fuel) in &self.jumps[system] {
+                if *jump_fuel > self.fuel_capacity {
+                    continue;
+                }
+                let next_cost = match criteria {
+                    RouteCriteria::Jumps => (cost.0 + 1.0, cost.1 + jump_fuel),
+                    RouteCriteria::Fuel => (cost.0 + jump_fuel, cost.1 + 1.0),
+                };
+                let improves = match best_costs[*next] {
+                    Some(best) => next_cost < best,
+                    None => true,
+                };
+                if improves {
+                    best_costs[*next] = Some(next_cost);
+                    previous[*next] = Some((system, *jump_fuel));
+                    candidates.push(Candidate {
+                        cost: next_cost,
+                        system: *next,
+                    });
+                }
+            }
+        }
+
+        best_costs[to]?;
+        let mut jumps = vec![];
+        let mut current = to;
+        while let Some((system, jump_fuel)) = previous[current] {
+            jumps.push((current, jump_fuel));
+            current = system;
+        }
+        jumps.reverse();
+
+        let mut fuel = min!(fuel, self.fuel_capacity);
+        let mut departure = from;
+        let mut steps = vec![];
+        for (index, (system, jump_fuel)) in jumps.into_iter().enumerate() {
+            if index > 0 {
+                fuel = min!(fuel + self.fuel_regeneration, self.fuel_capacity);
+            }
+            let refuel = fuel < jump_fuel;
+            if refuel {
+                if !self.has_spaceport[departure] {
+                    return None;
+                }
+                fuel = self.fuel_capacity;
+            }
+            fuel = max!(fuel - jump_fuel, 0.0);
+            steps.push(RouteStep {
+                system: self.systems[system].clone(),
+                fuel: jump_fuel,
+                refuel,
+            });
+            departure = system;
+        }
+
+        Some(Route {
+            origin,
+            fuel: steps.iter().map(|step| step.fuel).sum(),
+            steps,
+        })
+    }
+
+    fn index_of(&self, system: &str) -> Option<usize> {
+        self.systems
+            .iter()
+            .position(|candidate| candidate == system)
+    }
+}
+
+impl Game {
+    /// Find the best route from the current system of the player to another system
+    pub fn plan_route(&self, destination: &str, criteria: RouteCriteria) -> Option<Route> {
+        let flagship = self.player.flagship();
+        NavigationGraph::new(&self.systems, &self.planets, flagship).find_route(
+            &self.player.current_system.name,
+            destination,
+            criteria,
+            flagship.fuel,
+        )
+    }
+
+    /// hyperspace links between systems, each one only once
+    pub fn hyperspace_links(&self) -> Vec<(&Arc<System>, &Arc<System>)> {
+        let mut links = vec![];
+        for (index, system) in self.systems.iter().enumerate() {
+            for other in &self.systems[index + 1..] {
+                if system.is_linked_to(other) || other.is_linked_to(system) {
+                    links.push((system, other));
+                }
+            }
+        }
+        links
+    }
+
+    /// the system closest to a position of the universe map, if one is close enough
+    pub fn system_at(
+        &self,
+        position: Vector2D<f32, UnknownUnit>,
+        radius: f32,
+    ) -> Option<&Arc<System>> {
+        self.systems
+            .iter()
+            .map(|system| (system, (system.position - position).length()))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|(_, first), (_, second)| first.partial_cmp(second).unwrap_or(Ordering::Equal))
+            .map(|(system, _)| system)
+    }
+
+    /// Jump to another system, using fuel and taking a day
+    pub fn jump_to(&mut self, system: &str) -> Result<Vec<LogEntry>, JumpError> {
+        let destination = self
+            .systems
+            .iter()
+            .find(|candidate| candidate.name == system)
+            .ok_or_else(|| JumpError::UnknownSystem(String::from(system)))?
+            .clone();
+
+        let flagship = self.player.flagship();
+        let needed = flagship
+            .get_jump_fuel(&self.player.current_system, &destination)
+            .ok_or_else(|| JumpError::Unreachable(String::from(system)))?;
+        if needed > flagship.fuel {
+            return Err(JumpError::NotEnoughFuel {
+                needed,
+                available: flagship.fuel,
+            });
+        }
+
+        self.player.flagship_mut().fuel -= needed;
+        self.player.current_system = destination;
+        self.player.bribed_planets.clear();
+        self.player.exploration.visit_system(system);
+        Ok(self.advance_days(1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_data;
+
+    const OUTFITS: &str = r#"outfit "Jump Drive"
+	category "Systems"
+	"mass" 30
+	"jump drive" 1
+
+outfit "Heavy Jump Drive"
+	category "Systems"
+	"mass" 40
+	"jump drive" 1
+	"jump fuel" 300
+
+outfit "Fuel Pod"
+	category "Systems"
+	"mass" 5
+	"fuel capacity" 100
+
+outfit "Leaky Tank"
+	category "Systems"
+	"mass" 5
+	"fuel capacity" -200
+
+outfit "Ramscoop"
+	category "Systems"
+	"mass" 5
+	"ramscoop" 25
+"#;
+
+    const HOME: &str = "\tlink Neighbour\n";
+
+    const SYSTEMS: &str = r#"system Neighbour
+	pos 200 0
+	government Republic
+	habitable 100
+	link Home
+	link Outpost
+
+system Outpost
+	pos 400 0
+	government Republic
+	habitable 100
+	link Neighbour
+
+system Nearby
+	pos 50 50
+	government Republic
+	habitable 100
+"#;
+
+    const ROUTE_HOME: &str = "\tlink A\n\tobject \"Home\"\n\t\tperiod 10\n";
+
+    const ROUTE_SYSTEMS: &str = r#"system A
+	pos 60 0
+	government Republic
+	habitable 100
+	link Home
+	link B
+
+system B
+	pos 90 0
+	government Republic
+	habitable 100
+	link A
+	link C
+
+system C
+	pos 200 0
+	government Republic
+	habitable 100
+	link B
+	link D
+	object "Depot"
+		period 10
+
+system D
+	pos 300 0
+	government Republic
+	habitable 100
+	link C
+
+planet "Depot"
+	description "A fuel depot."
+	spaceport "A spaceport."
+"#;
+
+    /// a game on a map where "Home" has the given links, the shuttle of the player having the
+    /// given outfits instead of it's hyperdrive, and a full tank
+    fn game_on(home: &str, systems: &str, outfits: &[&str]) -> Game {
+        let system = format!("{}{}", test_data::SYSTEM, home);
+        let data = test_data::data_from(&[
+            test_data::SHIPS,
+            test_data::PLANET,
+            &system,
+            test_data::START,
+            OUTFITS,
+            systems,
+        ]);
+        let mut game = data.create_game().unwrap();
+        let flagship = game.player.flagship_mut();
+        flagship.outfits = outfits
+            .iter()
+            .map(|name| (data.get_outfit(name).unwrap().clone(), 1))
+            .collect();
+        flagship.fuel = flagship.get_fuel_capacity();
+        game
+    }
+
+    fn game(outfits: &[&str]) -> Game {
+        game_on(HOME, SYSTEMS, outfits)
+    }
+
+    fn route_game(outfits: &[&str]) -> Game {
+        game_on(ROUTE_HOME, ROUTE_SYSTEMS, outfits)
+    }
+
+    #[test]
+    fn can_find_links_and_systems_on_the_map() {
+        let game = game(&["Hyperdrive"]);
+
+        let links = game
+            .hyperspace_links()
+            .into_iter()
+            .map(|(first, second)| (first.name.as_str(), second.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(links, vec![("Home", "Neighbour"), ("Neighbour", "Outpost")]);
+
+        let name_at = |x: f32, y: f32, radius: f32| {
+            game.system_at(euclid::vec2(x, y), radius)
+                .map(|system| system.name.clone())
+        };
+        assert_eq!(name_at(45.0, 45.0, 20.0), Some(String::from("Nearby")));
+        assert_eq!(name_at(20.0, 20.0, 50.0), Some(String::from("Home")));
+        assert_eq!(name_at(100.0, 0.0, 20.0), None);
+    }
+
+    #[test]
+    fn can_jump_to_linked_systems() {
+        let mut game = game(&["Hyperdrive"]);
+        assert_eq!(game.player.flagship().fuel, 400.0);
+        let start_date = game.current_date;
+
+        assert_eq!(
+            game.jump_to("Nowhere").unwrap_err(),
+            JumpError::UnknownSystem(String::from("Nowhere"))
+        );
+        assert_eq!(
+            game.jump_to("Nearby").unwrap_err(),
+            JumpError::Unreachable(String::from("Nearby"))
+        );
+
+        game.jump_to("Neighbour").unwrap();
+        assert_eq!(game.player.current_system.name, "Neighbour");
+        assert_eq!(game.player.flagship().fuel, 300.0);
+        assert_eq!(game.get_nb_days_elapsed_since(start_date), 1);
+
+        game.jump_to("Outpost").unwrap();
+        game.jump_to("Neighbour").unwrap();
+        game.jump_to("Home").unwrap();
+        assert_eq!(
+            game.jump_to("Neighbour").unwrap_err(),
+            JumpError::NotEnoughFuel {
+                needed: 100.0,
+                available: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn can_jump_to_systems_in_range_with_a_jump_drive() {
+        let mut game = game(&["Jump Drive"]);
+
+        game.jump_to("Nearby").unwrap();
+        assert_eq!(game.player.current_system.name, "Nearby");
+        assert_eq!(game.player.flagship().fuel, 200.0);
+        assert_eq!(
+            game.jump_to("Outpost").unwrap_err(),
+            JumpError::Unreachable(String::from("Outpost"))
+        );
+    }
+
+    #[test]
+    fn can_find_routes_by_jumps_or_fuel() {
+        let mut game = route_game(&["Hyperdrive", "Heavy Jump Drive", "Fuel Pod"]);
+
+        let by_jumps = game.plan_route("B", RouteCriteria::Jumps).unwrap();
+        assert_eq!(by_jumps.jumps(), 1);
+        assert_eq!(by_jumps.fuel, 300.0);
+
+        let by_fuel = game.plan_route("B", RouteCriteria::Fuel).unwrap();
+        assert_eq!(
+            by_fuel
+                .steps
+                .iter()
+                .map(|step| step.system.as_str())
+                .collect::<Vec<_>>(),
+            vec!["A", "B"]
+        );
+        assert_eq!(by_fuel.fuel, 200.0);
+
+        for step in by_fuel.steps {
+            game.jump_to(&step.system).unwrap();
+        }
+        assert_eq!(game.player.current_system.name, "B");
+        assert!(game.plan_route("Nowhere", RouteCriteria::Jumps).is_none());
+    }
+
+    #[test]
+    fn can_tell_where_to_refuel() {
+        let mut game = route_game(&["Hyperdrive"]);
+        game.player.flagship_mut().fuel = 300.0;
+
+        let route = game.plan_route("D", RouteCriteria::Jumps).unwrap();
+        assert_eq!(route.jumps(), 4);
+        assert_eq!(
+            route
+                .steps
+                .iter()
+                .map(|step| step.refuel)
+                .collect::<Vec<_>>(),
+            vec![false, false, false, true]
+        );
+        assert_eq!(route.refuel_stops(), vec!["C"]);
+
+        let mut game = route_game(&["Hyperdrive"]);
+        game.player.flagship_mut().fuel = 50.0;
+        let route = game.plan_route("D", RouteCriteria::Jumps).unwrap();
+        assert_eq!(route.refuel_stops(), vec!["Home"]);
+
+        let mut game = route_game(&["Hyperdrive", "Ramscoop"]);
+        game.player.flagship_mut().fuel = 300.0;
+        let route = game.plan_route("D", RouteCriteria::Jumps).unwrap();
+        assert!(route.refuel_stops().is_empty());
+
+        // "Home" has no planet with a spaceport to refuel on
+        let mut game = game_on("\tlink A\n", ROUTE_SYSTEMS, &["Hyperdrive"]);
+        game.player.flagship_mut().fuel = 50.0;
+        assert!(game.plan_route("D", RouteCriteria::Jumps).is_none());
+    }
+
+    #[test]
+    fn cant_find_routes_with_jumps_needing_more_fuel_than_the_tank_holds() {
+        let game = route_game(&["Heavy Jump Drive", "Leaky Tank"]);
+        assert_eq!(game.player.flagship().get_fuel_capacity(), 200.0);
+        assert!(game.plan_route("A", RouteCriteria::Jumps).is_none());
+
+        let game = route_game(&["Heavy Jump Drive"]);
+        assert_eq!(
+            game.plan_route("A", RouteCriteria::Jumps).unwrap().jumps(),
+            1
+        );
+    }
+}
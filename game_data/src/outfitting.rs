@@ -0,0 +1,296 @@
+use super::data::{Outfit, ShipInstance};
+use super::stats::Capacity;
+use super::{InstallError, Space};
+
+/// spaces an outfit adds to a ship, or uses when negative
+fn outfit_spaces(outfit: &Outfit) -> Vec<(Space, f32)> {
+    let attributes = &outfit.attributes;
+    vec![
+        (Space::CargoSpace, attributes.cargo_space),
+        (Space::OutfitSpace, attributes.outfit_space),
+        (Space::WeaponCapacity, attributes.weapon_capacity),
+        (Space::EngineCapacity, attributes.engine_capacity),
+        (Space::GunPorts, attributes.gun_ports),
+        (Space::TurretMounts, attributes.turret_mounts),
+    ]
+    .into_iter()
+    .chain(
+        outfit
+            .ammo_capacity
+            .iter()
+            .map(|(kind, capacity)| (Space::Ammo(kind.clone()), *capacity)),
+    )
+    .filter(|(_, value)| *value != 0.0)
+    .collect()
+}
+
+impl ShipInstance {
+    /// get every space of the ship, with how much of it it's outfits use
+    pub fn get_capacities(&self) -> Vec<(Space, Capacity)> {
+        let mut capacities: Vec<(Space, Capacity)> = self.stats().capacities().to_vec();
+        for (outfit, count) in &self.outfits {
+            for (kind, capacity) in &outfit.ammo_capacity {
+                let space = Space::Ammo(kind.clone());
+                let index = match capacities.iter().position(|(found, _)| *found == space) {
+                    Some(index) => index,
+                    None => {
+                        capacities.push((space, Capacity::default()));
+                        capacities.len() - 1
+                    }
+                };
+                let total = capacity * (*count as f32);
+                if total > 0.0 {
+                    capacities[index].1.total += total;
+                } else {
+                    capacities[index].1.used -= total;
+                }
+            }
+        }
+        capacities
+    }
+
+    /// get the number of an outfit installed on the ship
+    pub fn get_outfit_count(&self, name: &str) -> u32 {
+        self.outfits
+            .iter()
+            .find(|(outfit, _)| outfit.name == name)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+
+    /// check that adding (or removing when `change` is negative) outfits doesn't use more of a
+    /// space than the ship has. A space already overused is only refused if it gets worse.
+    fn check_space_change(&self, outfit: &Outfit, change: i64) -> Result<(), InstallError> {
+        let capacities = self.get_capacities();
+        for (space, value) in outfit_spaces(outfit) {
+            let delta = value * (change as f32);
+            if delta >= 0.0 {
+                continue;
+            }
+            let available = capacities
+                .iter()
+                .find(|(found, _)| *found == space)
+                .map(|(_, capacity)| capacity.free())
+                .unwrap_or(0.0);
+            if available + delta < 0.0 {
+                return Err(InstallError::NotEnoughSpace {
+                    space,
+                    needed: -delta,
+                    available,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// check if a number of an outfit can be installed on the ship
+    pub fn can_install(&self, outfit: &Outfit, count: u32) -> Result<(), InstallError> {
+        self.check_space_change(outfit, i64::from(count))
+    }
+
+    /// install a number of an outfit on the ship
+    pub fn install(&mut self, outfit: &Outfit, count: u32) -> Result<(), InstallError> {
+        self.can_install(outfit, count)?;
+        match self
+            .outfits
+            .iter_mut()
+            .find(|(installed, _)| installed.name == outfit.name)
+        {
+            Some((_, installed_count)) => *installed_count += count,
+            None => self.outfits.push((outfit.clone(), count)),
+        }
+        Ok(())
+    }
+
+    /// remove a number of an outfit from the ship, which can be refused if what it was holding
+    /// would not fit anymore
+    pub fn uninstall(&mut self, name: &str, count: u32) -> Result<Outfit, InstallError> {
+        let installed = self.get_outfit_count(name);
+        let outfit = match self.outfits.iter().find(|(outfit, _)| outfit.name == name) {
+            Some((outfit, _)) if installed >= count => outfit.clone(),
+            _ => {
+                return Err(InstallError::NotInstalled {
+                    outfit: String::from(name),
+                    installed,
+                })
+            }
+        };
+        self.check_space_change(&outfit, -i64::from(count))?;
+        for (installed_outfit, installed_count) in self.outfits.iter_mut() {
+            if installed_outfit.name == name {
+                *installed_count -= count;
+            }
+        }
+        self.outfits.retain(|(_, count)| *count > 0);
+        Ok(outfit)
+    }
+
+    /// check that the installed outfits fit in the ship
+    pub fn check_outfits(&self) -> Vec<InstallError> {
+        self.get_capacities()
+            .into_iter()
+            .filter(|(_, capacity)| capacity.is_overused())
+            .map(|(space, capacity)| InstallError::NotEnoughSpace {
+                space,
+                needed: capacity.used,
+                available: capacity.total,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::data::ShipInstance;
+    use crate::test_data;
+    use crate::{InstallError, Space};
+
+    const DATA: &str = r#"outfit "Blaster"
+	category "Guns"
+	"mass" 3
+	"outfit space" -3
+	"weapon capacity" -3
+	"gun ports" -1
+
+outfit "Rocket Launcher"
+	category "Secondary Weapons"
+	"mass" 2
+	"outfit space" -2
+	"weapon capacity" -1
+	"gun ports" -1
+	"rocket capacity" 2
+
+outfit "Rocket"
+	category "Ammunition"
+	"mass" 1
+	"rocket capacity" -1
+
+outfit "Turret"
+	category "Turrets"
+	"mass" 10
+	"outfit space" -10
+	"weapon capacity" -10
+	"turret mounts" -1
+
+ship "Overloaded Gunboat"
+	sprite "ship/gunboat"
+	thumbnail "thumbnail/gunboat"
+	attributes
+		category "Light Warship"
+		"cost" 200000
+		"hull" 1200
+		"mass" 120
+		"drag" 2
+		"heat dissipation" .6
+		"outfit space" 20
+		"weapon capacity" 8
+		"engine capacity" 20
+		weapon
+			"blast radius" 12
+			"shield damage" 120
+			"hull damage" 60
+			"hit force" 180
+	outfits
+		"Blaster" 2
+	engine -9 38
+	gun 0 -30
+	explode "tiny explosion" 10
+	description "A gunboat with more blasters than guns."
+"#;
+
+    #[test]
+    fn can_install_outfits_within_capacities() {
+        let data = test_data::data_from(&[test_data::SHIPS, DATA]);
+        let model = Arc::new(data.get_ship("Shuttle").unwrap().clone());
+        let mut ship = ShipInstance::new("Shuttle", model);
+        let outfit = |name: &str| data.get_outfit(name).unwrap().clone();
+
+        assert_eq!(
+            ship.can_install(&outfit("Turret"), 1),
+            Err(InstallError::NotEnoughSpace {
+                space: Space::WeaponCapacity,
+                needed: 10.0,
+                available: 6.0,
+            })
+        );
+        assert_eq!(
+            ship.can_install(&outfit("Turret"), 1)
+                .unwrap_err()
+                .to_string(),
+            "not enough weapon capacity: 10 needed, 6 available"
+        );
+        assert_eq!(
+            ship.can_install(&outfit("Blaster"), 3),
+            Err(InstallError::NotEnoughSpace {
+                space: Space::WeaponCapacity,
+                needed: 9.0,
+                available: 6.0,
+            })
+        );
+        assert_eq!(
+            ship.can_install(&outfit("Rocket"), 1),
+            Err(InstallError::NotEnoughSpace {
+                space: Space::Ammo(String::from("rocket")),
+                needed: 1.0,
+                available: 0.0,
+            })
+        );
+
+        ship.install(&outfit("Blaster"), 1).unwrap();
+        ship.install(&outfit("Rocket Launcher"), 1).unwrap();
+        ship.install(&outfit("Rocket"), 2).unwrap();
+        assert_eq!(ship.get_outfit_count("Rocket"), 2);
+        assert!(ship.install(&outfit("Rocket"), 1).is_err());
+        assert_eq!(
+            ship.can_install(&outfit("Rocket Launcher"), 1),
+            Err(InstallError::NotEnoughSpace {
+                space: Space::GunPorts,
+                needed: 1.0,
+                available: 0.0,
+            })
+        );
+
+        assert_eq!(
+            ship.uninstall("Rocket Launcher", 1).unwrap_err(),
+            InstallError::NotEnoughSpace {
+                space: Space::Ammo(String::from("rocket")),
+                needed: 2.0,
+                available: 0.0,
+            }
+        );
+        ship.uninstall("Rocket", 2).unwrap();
+        ship.uninstall("Rocket Launcher", 1).unwrap();
+        assert_eq!(ship.get_outfit_count("Rocket Launcher"), 0);
+        assert_eq!(
+            ship.uninstall("Blaster", 2).unwrap_err(),
+            InstallError::NotInstalled {
+                outfit: String::from("Blaster"),
+                installed: 1,
+            }
+        );
+        assert!(ship.check_outfits().is_empty());
+    }
+
+    #[test]
+    fn can_warn_about_ships_with_too_many_outfits() {
+        let data = test_data::data_from(&[test_data::SHIPS, DATA]);
+
+        let model = Arc::new(data.get_ship("Overloaded Gunboat").unwrap().clone());
+        assert_eq!(
+            ShipInstance::new("Overloaded Gunboat", model).check_outfits(),
+            vec![InstallError::NotEnoughSpace {
+                space: Space::GunPorts,
+                needed: 2.0,
+                available: 1.0,
+            }]
+        );
+        assert_eq!(
+            data.warnings(),
+            &[String::from(
+                "ship 'Overloaded Gunboat': not enough gun ports: 2 needed, 1 available"
+            )]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::data::{Government, Planet, System};
+use super::Game;
+
+/// An action of the player toward a ship of a government
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShipEvent {
+    /// helping a ship, by repairing or refueling it
+    Assist,
+    /// disabling a ship
+    Disable,
+    /// boarding a ship to plunder it
+    Board,
+    /// capturing a ship
+    Capture,
+    /// destroying a ship
+    Destroy,
+    /// doing something horrible, like killing a whole crew
+    Atrocity,
+}
+
+impl ShipEvent {
+    /// all the events, to iterate on them
+    pub const ALL: [ShipEvent; 6] = [
+        ShipEvent::Assist,
+        ShipEvent::Disable,
+        ShipEvent::Board,
+        ShipEvent::Capture,
+        ShipEvent::Destroy,
+        ShipEvent::Atrocity,
+    ];
+
+    /// it's name in the data files
+    pub fn name(self) -> &'static str {
+        match self {
+            ShipEvent::Assist => "assist",
+            ShipEvent::Disable => "disable",
+            ShipEvent::Board => "board",
+            ShipEvent::Capture => "capture",
+            ShipEvent::Destroy => "destroy",
+            ShipEvent::Atrocity => "atrocity",
+        }
+    }
+
+    /// reputation lost by default for it, negative when reputation is gained
+    pub fn default_penalty(self) -> f32 {
+        match self {
+            ShipEvent::Assist => -0.1,
+            ShipEvent::Disable => 0.5,
+            ShipEvent::Board => 0.3,
+            ShipEvent::Capture => 1.0,
+            ShipEvent::Destroy => 1.0,
+            ShipEvent::Atrocity => 10.0,
+        }
+    }
+}
+
+/// Reputation of the player with each government
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reputations {
+    values: Vec<(String, f32)>,
+}
+
+impl Reputations {
+    /// get the reputation with a government, 0 if it was never set
+    pub fn get(&self, government: &str) -> f32 {
+        self.values
+            .iter()
+            .find(|(name, _)| name == government)
+            .map(|(_, value)| *value)
+            .unwrap_or(0.0)
+    }
+
+    /// set the reputation with a government
+    pub fn set(&mut self, government: &str, value: f32) {
+        match self.values.iter_mut().find(|(name, _)| name == government) {
+            Some((_, reputation)) => *reputation = value,
+            None => self.values.push((String::from(government), value)),
+        }
+    }
+
+    /// add to the reputation with a government
+    pub fn add(&mut self, government: &str, value: f32) {
+        self.set(government, self.get(government) + value);
+    }
+
+    /// reputations with every government that has one
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+impl Game {
+    /// Find a government by it's name
+    pub fn get_government(&self, name: &str) -> Option<&Arc<Government>> {
+        self.governments
+            .iter()
+            .find(|government| government.name == name)
+    }
+
+    /// government of a planet, which is the one of it's system unless it has it's own
+    pub fn planet_government(&self, planet: &Planet, system: &System) -> Option<&Arc<Government>> {
+        self.get_government(planet.government.as_ref().unwrap_or(&system.government))
+    }
+
+    /// color of a government on the map, if it has one
+    pub fn government_color(&self, name: &str) -> Option<(f32, f32, f32)> {
+        self.get_government(name)
+            .and_then(|government| government.color)
+    }
+
+    /// reputation of the player with a government
+    pub fn reputation(&self, government: &str) -> f32 {
+        self.player.reputations.get(government)
+    }
+
+    /// is a government hostile to the player
+    pub fn is_hostile(&self, government: &str) -> bool {
+        self.reputation(government) < 0.0
+    }
+
+    /// is a government friendly to the player, and not just tolerating it
+    pub fn is_friendly(&self, government: &str) -> bool {
+        self.reputation(government) > 0.0
+    }
+
+    /// are two governments enemies, one of them being hostile toward the other
+    pub fn are_enemies(&self, first: &str, second: &str) -> bool {
+        if first == second {
+            return false;
+        }
+        let attitude = |from: &str, to: &str| {
+            self.get_government(from)
+                .map(|government| government.attitude_toward(to))
+                .unwrap_or(0.0)
+        };
+        attitude(first, second) < 0.0 || attitude(second, first) < 0.0
+    }
+
+    /// Change the reputation of the player after it did something to a ship of a government.
+    /// Governments that like the one of the ship change their reputation too.
+    pub fn record_ship_event(&mut self, government: &str, event: ShipEvent) {
+        for other in &self.governments {
+            let weight = other.attitude_toward(government);
+            if weight > 0.0 {
+                self.player
+                    .reputations
+                    .add(&other.name, -weight * other.penalty_for(event));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_data;
+
+    const DATA: &str = r#"government "Republic"
+	color 1 .5 .25
+	"player reputation" 10
+	"attitude toward"
+		"Merchant" .5
+		"Pirate" -.5
+	"penalty for"
+		assist -.5
+		destroy 2
+
+government "Merchant"
+	"player reputation" 1
+	"attitude toward"
+		"Republic" .25
+	"penalty for"
+		assist -.5
+
+government "Pirate"
+	"player reputation" -10
+	"attitude toward"
+		"Republic" -1
+		"Merchant" -1
+
+planet "Den"
+	government "Pirate"
+	description "A pirate den."
+"#;
+
+    fn game() -> Game {
+        test_data::game_with(DATA)
+    }
+
+    #[test]
+    fn can_tell_hostile_and_friendly_governments() {
+        let game = game();
+        assert_eq!(game.reputation("Republic"), 10.0);
+        assert!(game.is_friendly("Republic"));
+        assert!(game.is_hostile("Pirate"));
+        assert!(!game.is_hostile("Independent"));
+        assert!(!game.is_friendly("Independent"));
+        assert_eq!(game.government_color("Republic"), Some((1.0, 0.5, 0.25)));
+        assert_eq!(game.government_color("Pirate"), None);
+
+        assert!(game.are_enemies("Republic", "Pirate"));
+        assert!(game.are_enemies("Merchant", "Pirate"));
+        assert!(!game.are_enemies("Republic", "Merchant"));
+        assert!(!game.are_enemies("Pirate", "Pirate"));
+
+        let system = game.systems[0].clone();
+        let planet = |name: &str| {
+            game.planets
+                .iter()
+                .find(|planet| planet.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            game.planet_government(&planet("Home"), &system)
+                .map(|government| government.name.as_str()),
+            Some("Republic")
+        );
+        assert_eq!(
+            game.planet_government(&planet("Den"), &system)
+                .map(|government| government.name.as_str()),
+            Some("Pirate")
+        );
+    }
+
+    #[test]
+    fn can_change_reputation_with_ship_events() {
+        let mut game = game();
+
+        game.record_ship_event("Pirate", ShipEvent::Destroy);
+        assert_eq!(game.reputation("Pirate"), -11.0);
+        assert_eq!(game.reputation("Republic"), 10.0);
+
+        game.record_ship_event("Merchant", ShipEvent::Assist);
+        assert_eq!(game.reputation("Merchant"), 1.5);
+        assert_eq!(game.reputation("Republic"), 10.25);
+
+        game.record_ship_event("Republic", ShipEvent::Destroy);
+        assert_eq!(game.reputation("Republic"), 8.25);
+        assert_eq!(game.reputation("Merchant"), 1.25);
+        assert_eq!(game.reputation("Pirate"), -11.0);
+    }
+}
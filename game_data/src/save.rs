@@ -0,0 +1,305 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use helpers::max;
+
+use super::SaveError;
+
+/// version of the save format written by this version of the game
+pub const SAVE_VERSION: u32 = 10;
+
+/// A saved game, referencing data objects by their name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// version of the save format
+    pub version: u32,
+    /// current date, as year, month and day
+    pub date: (i32, u32, u32),
+    /// account of the player
+    pub account: crate::account::Account,
+    /// name of the system the player is in
+    pub current_system: String,
+    /// name of the planet the player is landed on
+    pub current_planet: Option<String>,
+    /// ships of the player
+    pub ships: Vec<SavedShip>,
+    /// index of the ship the player is flying
+    pub flagship: usize,
+    /// conditions of the player
+    pub conditions: Vec<(String, i64)>,
+    /// when the player bought it's ships and outfits
+    pub depreciation: crate::shop::Depreciation,
+    /// reputation of the player with each government
+    pub reputations: crate::politics::Reputations,
+    /// systems and planets the player visited or knows about
+    pub exploration: crate::exploration::Exploration,
+    /// missions the player accepted, and events waiting to happen
+    pub missions: crate::missions::Missions,
+}
+
+/// A saved ship, with it's installed outfits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedShip {
+    /// name of the ship model
+    pub model: String,
+    /// name given to the ship
+    pub name: String,
+    /// names of the installed outfits, with their count
+    pub outfits: Vec<(String, u32)>,
+    /// commodities in it's hold, with their quantity in tons
+    pub cargo: Vec<(String, u32)>,
+    /// cargo of missions in it's hold, by mission, in tons
+    pub mission_cargo: Vec<(String, u32)>,
+    /// names of the outfits in it's hold, with their count
+    pub cargo_outfits: Vec<(String, u32)>,
+    /// hull left
+    pub hull: f32,
+    /// shields left
+    pub shields: f32,
+    /// fuel left
+    pub fuel: f32,
+    /// name of the planet it's parked on
+    pub parked_on: Option<String>,
+}
+
+impl SavedShip {
+    /// Save the state of a ship of the player
+    pub fn from_ship_instance(ship: &crate::data::ShipInstance) -> Self {
+        SavedShip {
+            model: ship.model.name.clone(),
+            name: ship.name.clone(),
+            outfits: ship
+                .outfits
+                .iter()
+                .map(|(outfit, count)| (outfit.name.clone(), *count))
+                .collect(),
+            cargo: ship.cargo.commodities.clone(),
+            mission_cargo: ship.cargo.mission_cargo.clone(),
+            cargo_outfits: ship
+                .cargo
+                .outfits
+                .iter()
+                .map(|(outfit, count)| (outfit.name.clone(), *count))
+                .collect(),
+            hull: ship.hull,
+            shields: ship.shields,
+            fuel: ship.fuel,
+            parked_on: ship.parked_on.clone(),
+        }
+    }
+}
+
+impl SavedGame {
+    /// Save the state of a game
+    pub fn from_game(game: &super::Game) -> Self {
+        let mut conditions: Vec<(String, i64)> = game
+            .conditions
+            .iter()
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+        conditions.sort();
+
+        SavedGame {
+            version: SAVE_VERSION,
+            date: (
+                game.current_date.year(),
+                game.current_date.month(),
+                game.current_date.day(),
+            ),
+            account: game.player.account.clone(),
+            current_system: game.player.current_system.name.clone(),
+            current_planet: game
+                .player
+                .current_planet
+                .as_ref()
+                .map(|planet| planet.name.clone()),
+            ships: game
+                .player
+                .fleet
+                .iter()
+                .map(SavedShip::from_ship_instance)
+                .collect(),
+            flagship: game.player.flagship,
+            conditions,
+            depreciation: game.player.depreciation.clone(),
+            reputations: game.player.reputations.clone(),
+            exploration: game.player.exploration.clone(),
+            missions: game.player.missions.to_save(),
+        }
+    }
+
+    /// Save the state of an Endless Sky pilot, it's flagship being the first ship. Ships landed
+    /// on another planet than the pilot are parked there.
+    pub fn from_es_pilot(pilot: &es_data_parser::Pilot) -> Result<Self, SaveError> {
+        if pilot.ships.is_empty() {
+            return Err(SaveError::NoShip);
+        }
+
+        let mut conditions: Vec<(String, i64)> = pilot
+            .conditions
+            .iter()
+            .map(|(name, value)| (String::from(*name), *value))
+            .collect();
+        conditions.sort();
+
+        let mut reputations = crate::politics::Reputations::default();
+        for (government, reputation) in &pilot.reputations {
+            reputations.set(government, *reputation);
+        }
+
+        let mut exploration = crate::exploration::Exploration::default();
+        for system in &pilot.visited_systems {
+            exploration.visit_system(system);
+        }
+        for planet in &pilot.visited_planets {
+            exploration.visit_planet(planet);
+        }
+
+        Ok(SavedGame {
+            version: SAVE_VERSION,
+            date: (pilot.date.year, pilot.date.month, pilot.date.day),
+            account: crate::account::Account {
+                credits: pilot.account.credits as i64,
+                credit_score: pilot.account.score as i32,
+                mortgages: pilot
+                    .account
+                    .mortgages
+                    .iter()
+                    .map(|(kind, mortgage)| crate::account::Mortgage {
+                        kind: if *kind == "Fine" {
+                            crate::account::DebtKind::Fine
+                        } else {
+                            crate::account::DebtKind::Mortgage
+                        },
+                        ..crate::account::Mortgage::from_es_mortgage(mortgage)
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            current_system: String::from(pilot.system),
+            current_planet: pilot.planet.map(String::from),
+            ships: pilot
+                .ships
+                .iter()
+                .enumerate()
+                .map(|(index, ship)| SavedShip {
+                    model: String::from(ship.model),
+                    name: String::from(ship.name.unwrap_or(ship.model)),
+                    outfits: ship
+                        .outfits
+                        .iter()
+                        .map(|(name, count)| (String::from(*name), *count))
+                        .collect(),
+                    cargo: vec![],
+                    mission_cargo: vec![],
+                    cargo_outfits: vec![],
+                    hull: ship.hull.unwrap_or(0.0),
+                    shields: ship.shields.unwrap_or(0.0),
+                    fuel: ship.fuel.unwrap_or(0.0),
+                    parked_on: ship
+                        .planet
+                        .filter(|planet| index > 0 && Some(*planet) != pilot.planet)
+                        .map(String::from),
+                })
+                .collect(),
+            flagship: 0,
+            conditions,
+            depreciation: crate::shop::Depreciation::default(),
+            reputations,
+            exploration,
+            // missions of ES pilots are not imported
+            missions: crate::missions::Missions::default(),
+        })
+    }
+
+    /// Write the saved game as an Endless Sky pilot, it's flagship being the first ship
+    pub fn to_es_pilot(&self, first_name: &str, last_name: &str) -> String {
+        let (year, month, day) = self.date;
+        let mut pilot = format!("pilot {} {}\n", quote(first_name), quote(last_name));
+        pilot.push_str(&format!("date {} {} {}\n", day, month, year));
+        pilot.push_str(&format!("system {}\n", quote(&self.current_system)));
+        if let Some(planet) = &self.current_planet {
+            pilot.push_str(&format!("planet {}\n", quote(planet)));
+        }
+        pilot.push_str("account\n");
+        pilot.push_str(&format!("\tcredits {}\n", max!(self.account.credits, 0)));
+        pilot.push_str(&format!("\tscore {}\n", self.account.credit_score));
+        for mortgage in &self.account.mortgages {
+            pilot.push_str(&format!(
+                "\tmortgage {}\n",
+                match mortgage.kind {
+                    crate::account::DebtKind::Mortgage => "Mortgage",
+                    crate::account::DebtKind::Fine => "Fine",
+                }
+            ));
+            pilot.push_str(&format!("\t\tprincipal {}\n", max!(mortgage.principal, 0)));
+            pilot.push_str(&format!("\t\tinterest {}\n", mortgage.interest));
+            pilot.push_str(&format!("\t\tterm {}\n", mortgage.term));
+        }
+        let flagship = self.ships.get(self.flagship);
+        let others = self
+            .ships
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.flagship)
+            .map(|(_, ship)| ship);
+        for ship in flagship.into_iter().chain(others) {
+            pilot.push_str(&format!("ship {}\n", quote(&ship.model)));
+            pilot.push_str(&format!("\tname {}\n", quote(&ship.name)));
+            pilot.push_str("\toutfits\n");
+            for (outfit, count) in &ship.outfits {
+                pilot.push_str(&format!("\t\t{} {}\n", quote(outfit), count));
+            }
+            pilot.push_str(&format!("\tfuel {}\n", ship.fuel));
+            pilot.push_str(&format!("\tshields {}\n", ship.shields));
+            pilot.push_str(&format!("\thull {}\n", ship.hull));
+            match &ship.parked_on {
+                Some(planet) => {
+                    pilot.push_str(&format!("\tplanet {}\n", quote(planet)));
+                    pilot.push_str("\tparked\n");
+                }
+                None => {
+                    pilot.push_str(&format!("\tsystem {}\n", quote(&self.current_system)));
+                    if let Some(planet) = &self.current_planet {
+                        pilot.push_str(&format!("\tplanet {}\n", quote(planet)));
+                    }
+                }
+            }
+        }
+        pilot.push_str("\"reputation with\"\n");
+        for (government, reputation) in self.reputations.iter() {
+            pilot.push_str(&format!("\t{} {}\n", quote(government), reputation));
+        }
+        for system in self.exploration.visited_systems() {
+            pilot.push_str(&format!("visited {}\n", quote(system)));
+        }
+        for planet in self.exploration.visited_planets() {
+            pilot.push_str(&format!("\"visited planet\" {}\n", quote(planet)));
+        }
+        pilot.push_str("conditions\n");
+        for (name, value) in &self.conditions {
+            pilot.push_str(&format!("\t{} {}\n", quote(name), value));
+        }
+        pilot
+    }
+
+    /// Write the saved game to the content of a save file
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| SaveError::Format(error.to_string()))
+    }
+
+    /// Read a saved game from the content of a save file
+    pub fn from_ron(content: &str) -> Result<Self, SaveError> {
+        ron::de::from_str(content).map_err(|error| SaveError::Format(error.to_string()))
+    }
+}
+
+/// quote a token for an Endless Sky data file
+fn quote(token: &str) -> String {
+    if token.contains('"') {
+        format!("`{}`", token)
+    } else {
+        format!("\"{}\"", token)
+    }
+}
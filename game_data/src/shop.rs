@@ -0,0 +1,412 @@
+use std::sync::Arc;
+
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use super::data::{Planet, ShipInstance};
+use super::{Game, TradeError};
+
+/// share of it's price an item keeps once fully depreciated
+pub const FULL_DEPRECIATION: f64 = 0.25;
+/// share of it's depreciable value an item keeps each day
+pub const DAILY_DEPRECIATION: f64 = 0.997;
+
+/// share of it's price an item bought a number of days ago can be sold for
+pub fn depreciate(age: i32) -> f64 {
+    if age <= 0 {
+        1.0
+    } else {
+        FULL_DEPRECIATION + (1.0 - FULL_DEPRECIATION) * DAILY_DEPRECIATION.powi(age)
+    }
+}
+
+/// Days at which items were bought, by name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Records {
+    items: Vec<(String, Vec<i32>)>,
+}
+
+impl Records {
+    /// record that a number of items were bought on a day
+    pub fn buy(&mut self, name: &str, day: i32, count: u32) {
+        let index = match self.items.iter().position(|(item, _)| item == name) {
+            Some(index) => index,
+            None => {
+                self.items.push((String::from(name), vec![]));
+                self.items.len() - 1
+            }
+        };
+        let days = &mut self.items[index].1;
+        days.extend((0..count).map(|_| day));
+        days.sort();
+    }
+
+    /// credits a number of items can be sold for, the most recently bought being sold first.
+    /// Items bought before they were recorded are fully depreciated.
+    pub fn value(&self, name: &str, cost: u32, day: i32, count: u32) -> i64 {
+        let days: &[i32] = match self.items.iter().find(|(item, _)| item == name) {
+            Some((_, days)) => days,
+            None => &[],
+        };
+        let share: f64 = (0..count as usize)
+            .map(|index| match days.iter().rev().nth(index) {
+                Some(bought) => depreciate(day - bought),
+                None => FULL_DEPRECIATION,
+            })
+            .sum();
+        (f64::from(cost) * share).round() as i64
+    }
+
+    /// forget a number of sold items, the most recently bought being sold first
+    pub fn sell(&mut self, name: &str, count: u32) {
+        for (item, days) in self.items.iter_mut() {
+            if item == name {
+                let kept = days.len().saturating_sub(count as usize);
+                days.truncate(kept);
+            }
+        }
+        self.items.retain(|(_, days)| !days.is_empty());
+    }
+}
+
+/// When the player bought it's ships and outfits, to know how much they can be sold for
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Depreciation {
+    /// ships bought
+    pub ships: Records,
+    /// outfits bought, installed or not
+    pub outfits: Records,
+}
+
+impl Depreciation {
+    /// credits a ship can be sold for, with it's outfits
+    pub fn ship_value(&self, ship: &ShipInstance, day: i32) -> i64 {
+        self.ships.value(&ship.model.name, ship.model.cost, day, 1)
+            + ship
+                .outfits
+                .iter()
+                .map(|(outfit, count)| self.outfits.value(&outfit.name, outfit.cost, day, *count))
+                .sum::<i64>()
+    }
+
+    fn buy_ship(&mut self, ship: &ShipInstance, day: i32) {
+        self.ships.buy(&ship.model.name, day, 1);
+        for (outfit, count) in &ship.outfits {
+            self.outfits.buy(&outfit.name, day, *count);
+        }
+    }
+
+    fn sell_ship(&mut self, ship: &ShipInstance) {
+        self.ships.sell(&ship.model.name, 1);
+        for (outfit, count) in &ship.outfits {
+            self.outfits.sell(&outfit.name, *count);
+        }
+    }
+}
+
+impl Game {
+    fn day(&self) -> i32 {
+        self.current_date.num_days_from_ce()
+    }
+
+    fn landed_planet(&self) -> Result<Arc<Planet>, TradeError> {
+        self.player
+            .current_planet
+            .clone()
+            .ok_or(TradeError::NotLanded)
+    }
+
+    pub(crate) fn pay(&mut self, price: i64) -> Result<(), TradeError> {
+        if self.player.account.credits < price {
+            return Err(TradeError::NotEnoughCredits {
+                needed: price,
+                available: self.player.account.credits,
+            });
+        }
+        self.player.account.credits -= price;
+        Ok(())
+    }
+
+    fn check_licenses(&self, licenses: &[String]) -> Result<(), TradeError> {
+        match licenses
+            .iter()
+            .find(|license| self.conditions.get(&format!("license: {}", license)) <= 0)
+        {
+            Some(license) => Err(TradeError::MissingLicense(license.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// credits a number of outfits installed on the flagship can be sold for
+    pub fn outfit_sale_value(&self, name: &str, count: u32) -> i64 {
+        self.outfits
+            .iter()
+            .find(|outfit| outfit.name == name)
+            .map(|outfit| {
+                self.player
+                    .depreciation
+                    .outfits
+                    .value(name, outfit.cost, self.day(), count)
+            })
+            .unwrap_or(0)
+    }
+
+    /// credits a ship of the player can be sold for, with it's outfits
+    pub fn ship_sale_value(&self, index: usize) -> i64 {
+        self.player
+            .fleet
+            .get(index)
+            .map(|ship| self.player.depreciation.ship_value(ship, self.day()))
+            .unwrap_or(0)
+    }
+
+    /// Buy a number of an outfit from the outfitter of the planet, and install it on the
+    /// flagship. Maps are not installed, they reveal the systems around instead, and are bought
+    /// one at a time. Returns the credits paid.
+    pub fn buy_outfit(&mut self, name: &str, count: u32) -> Result<i64, TradeError> {
+        let planet = self.landed_planet()?;
+        if !planet.outfitter.iter().any(|outfit| outfit == name) {
+            return Err(TradeError::NotSold(String::from(name)));
+        }
+        let outfit = self
+            .outfits
+            .iter()
+            .find(|outfit| outfit.name == name)
+            .ok_or_else(|| TradeError::Unknown(String::from(name)))?
+            .clone();
+        self.check_licenses(&outfit.licenses)?;
+
+        let price = i64::from(outfit.cost) * i64::from(count);
+        if outfit.map > 0 {
+            if count != 1 {
+                return Err(TradeError::OnlyOne(String::from(name)));
+            }
+            self.pay(price)?;
+            self.reveal_map(outfit.map);
+            return Ok(price);
+        }
+        self.player.flagship().can_install(&outfit, count)?;
+        self.pay(price)?;
+        self.player.flagship_mut().install(&outfit, count)?;
+        let day = self.day();
+        self.player.depreciation.outfits.buy(name, day, count);
+        Ok(price)
+    }
+
+    /// Sell a number of an outfit installed on the flagship. Returns the credits received.
+    pub fn sell_outfit(&mut self, name: &str, count: u32) -> Result<i64, TradeError> {
+        self.landed_planet()?;
+        let value = self.outfit_sale_value(name, count);
+        self.player.flagship_mut().uninstall(name, count)?;
+        self.player.depreciation.outfits.sell(name, count);
+        self.player.account.credits += value;
+        Ok(value)
+    }
+
+    /// Buy a ship from the shipyard of the planet, with it's stock outfits, and add it to the
+    /// fleet. Returns the credits paid.
+    pub fn buy_ship(&mut self, name: &str) -> Result<i64, TradeError> {
+        let planet = self.landed_planet()?;
+        if !planet.shipyard.iter().any(|ship| ship == name) {
+            return Err(TradeError::NotSold(String::from(name)));
+        }
+        let model = self
+            .ships
+            .iter()
+            .find(|ship| ship.name == name)
+            .ok_or_else(|| TradeError::Unknown(String::from(name)))?
+            .clone();
+        self.check_licenses(&model.licenses)?;
+
+        let ship = ShipInstance::new(name, model);
+        let price = ship.get_cost();
+        self.pay(price)?;
+        let day = self.day();
+        self.player.depreciation.buy_ship(&ship, day);
+        self.player.fleet.push(ship);
+        Ok(price)
+    }
+
+    /// Sell a ship of the player, with it's outfits. It can't be the flagship. Returns the
+    /// credits received.
+    pub fn sell_ship(&mut self, index: usize) -> Result<i64, TradeError> {
+        self.landed_planet()?;
+        let value = self.ship_sale_value(index);
+        let sold = self.player.remove_ship(index)?;
+        self.player.depreciation.sell_ship(&sold);
+        self.player.account.credits += value;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_data;
+    use crate::{FleetError, InstallError, Space};
+
+    const DATA: &str = r#"outfit "Blaster"
+	category "Guns"
+	"cost" 1000
+	"mass" 3
+	"outfit space" -3
+	"weapon capacity" -3
+	"gun ports" -1
+
+outfit "Rare Blaster"
+	category "Guns"
+	"cost" 5000
+	"mass" 3
+	"outfit space" -3
+	"weapon capacity" -3
+	"gun ports" -1
+
+outfit "Navy Blaster"
+	category "Guns"
+	licenses
+		Navy
+	"cost" 2000
+	"mass" 3
+	"outfit space" -3
+	"weapon capacity" -3
+	"gun ports" -1
+
+outfitter "Common Outfits"
+	"Blaster"
+	"Navy Blaster"
+
+shipyard "Common Ships"
+	"Shuttle"
+	"Gunboat"
+
+ship "Gunboat"
+	sprite "ship/gunboat"
+	thumbnail "thumbnail/gunboat"
+	attributes
+		licenses
+			Military
+		category "Light Warship"
+		"cost" 200000
+		"hull" 1200
+		"mass" 120
+		"drag" 2
+		"heat dissipation" .6
+		"outfit space" 60
+		"weapon capacity" 30
+		"engine capacity" 30
+		weapon
+			"blast radius" 12
+			"shield damage" 120
+			"hull damage" 60
+			"hit force" 180
+	outfits
+		"Blaster" 2
+	engine -9 38
+	gun 0 -30
+	gun 4 -30
+	explode "tiny explosion" 10
+	description "A gunboat."
+"#;
+
+    fn game() -> Game {
+        test_data::game_with(DATA)
+    }
+
+    #[test]
+    fn can_depreciate_items() {
+        let mut records = Records::default();
+        records.buy("Blaster", 10, 1);
+        records.buy("Blaster", 0, 1);
+        assert_eq!(records.value("Blaster", 1000, 10, 1), 1000);
+        assert_eq!(records.value("Blaster", 1000, 10, 2), 1000 + 978);
+        assert_eq!(records.value("Blaster", 1000, 10, 3), 1000 + 978 + 250);
+        assert_eq!(records.value("Blaster", 1000, 10_000, 1), 250);
+
+        records.sell("Blaster", 1);
+        assert_eq!(records.value("Blaster", 1000, 10, 1), 978);
+        records.sell("Blaster", 1);
+        assert_eq!(records, Records::default());
+    }
+
+    #[test]
+    fn can_buy_and_sell_outfits() {
+        let mut game = game();
+        assert_eq!(game.player.current_planet.as_ref().unwrap().name, "Home");
+        assert_eq!(
+            game.player.current_planet.as_ref().unwrap().outfitter,
+            vec![String::from("Blaster"), String::from("Navy Blaster")]
+        );
+
+        assert_eq!(
+            game.buy_outfit("Rare Blaster", 1),
+            Err(TradeError::NotSold(String::from("Rare Blaster")))
+        );
+        assert_eq!(
+            game.buy_outfit("Navy Blaster", 1),
+            Err(TradeError::MissingLicense(String::from("Navy")))
+        );
+        assert_eq!(game.buy_outfit("Blaster", 2), Ok(2000));
+        assert_eq!(game.player.account.credits, 8000);
+        assert_eq!(game.player.flagship().get_outfit_count("Blaster"), 2);
+        assert_eq!(
+            game.buy_outfit("Blaster", 1),
+            Err(TradeError::Install(InstallError::NotEnoughSpace {
+                space: Space::WeaponCapacity,
+                needed: 3.0,
+                available: 0.0,
+            }))
+        );
+        assert_eq!(game.player.account.credits, 8000);
+
+        game.advance_days(10);
+        assert_eq!(game.outfit_sale_value("Blaster", 2), 2 * 978);
+        let credits = game.player.account.credits;
+        assert_eq!(game.sell_outfit("Blaster", 2), Ok(2 * 978));
+        assert_eq!(game.player.account.credits, credits + 2 * 978);
+        assert_eq!(game.player.flagship().get_outfit_count("Blaster"), 0);
+        assert!(game.sell_outfit("Blaster", 1).is_err());
+
+        game.player.current_planet = None;
+        assert_eq!(game.buy_outfit("Blaster", 1), Err(TradeError::NotLanded));
+    }
+
+    #[test]
+    fn can_buy_and_sell_ships() {
+        let mut game = game();
+        assert_eq!(game.ship_sale_value(0), 25_000);
+        game.buy_outfit("Blaster", 1).unwrap();
+        game.advance_days(10);
+
+        assert_eq!(
+            game.buy_ship("Gunboat"),
+            Err(TradeError::MissingLicense(String::from("Military")))
+        );
+        game.conditions.set("license: Military", 1);
+        assert_eq!(
+            game.buy_ship("Gunboat"),
+            Err(TradeError::NotEnoughCredits {
+                needed: 202_000,
+                available: 9_000,
+            })
+        );
+
+        game.player.account.credits = 300_000;
+        assert_eq!(game.buy_ship("Gunboat"), Ok(202_000));
+        assert_eq!(game.player.fleet.len(), 2);
+        assert_eq!(game.player.fleet[1].model.name, "Gunboat");
+        assert_eq!(game.player.account.credits, 98_000);
+        assert_eq!(game.ship_sale_value(1), 202_000);
+
+        assert_eq!(
+            game.sell_ship(0),
+            Err(TradeError::Fleet(FleetError::IsFlagship))
+        );
+        // outfits are sold newest first, so the older blaster of the shuttle is sold at the price
+        // of the blasters bought with the gunboat
+        game.player.set_flagship(1).unwrap();
+        assert_eq!(game.sell_ship(0), Ok(25_000 + 1000));
+        assert_eq!(game.player.fleet.len(), 1);
+        assert_eq!(game.player.flagship().model.name, "Gunboat");
+        assert_eq!(game.player.account.credits, 98_000 + 26_000);
+    }
+}
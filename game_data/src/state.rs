@@ -4,6 +4,11 @@ use gdnative::*;
 
 type OwnerNode = Node;
 
+const DATA_DIRECTORY: &str = "res://data";
+/// how often data files are checked for changes, in seconds
+const DATA_CHECK_INTERVAL: f32 = 1.0;
+const SAVE_FILE: &str = "user://save.ron";
+
 /// State of a game
 #[derive(NativeClass, Debug)]
 #[inherit(OwnerNode)]
@@ -14,51 +19,190 @@ pub struct State {
     pub game_data: crate::ESGameLoader,
     /// current state, with actions made by the player
     pub current_game: Option<crate::Game>,
+    data_loader: crate::loader::UnresolvedESGameLoader,
+    data_files_modified_time: Vec<(String, i64)>,
+    watch_data_files: bool,
+    time_since_last_check: f32,
 }
 
 unsafe impl Send for State {}
 
+/// content of a file, if it still exists and can be opened
+fn read_file(path: &str) -> Option<String> {
+    if !gdnative::File::new().file_exists(path.into()) {
+        return None;
+    }
+    let mut game_data_file = gdnative::File::new();
+    game_data_file.open(path.into(), 1).ok()?;
+    let content = game_data_file.get_as_text().to_string();
+    game_data_file.close();
+    Some(content)
+}
+
+fn modified_time(path: &str) -> i64 {
+    gdnative::File::new().get_modified_time(path.into())
+}
+
+/// paths of the data files currently in the data directory
+fn data_files() -> Vec<String> {
+    let mut paths = vec![];
+    let mut data_dir = gdnative::Directory::new();
+    if data_dir.open(DATA_DIRECTORY.into()).is_err()
+        || data_dir.list_dir_begin(false, false).is_err()
+    {
+        return paths;
+    }
+    loop {
+        let path = data_dir.get_next();
+        if path.is_empty() {
+            break;
+        }
+        if !path.ends_with(&".txt".into()) {
+            continue;
+        }
+        paths.push(format!("{}/{}", DATA_DIRECTORY, path.to_string()));
+    }
+    data_dir.list_dir_end();
+    paths
+}
+
+fn resolve(data_loader: &crate::loader::UnresolvedESGameLoader) -> crate::ESGameLoader {
+    let game_data = data_loader.resolve();
+    for warning in game_data.warnings() {
+        godot_print!("warning: {}", warning);
+    }
+    game_data
+}
+
 #[methods]
 impl State {
     fn _init(_owner: OwnerNode) -> Self {
         let mut es_game_data = crate::loader::UnresolvedESGameLoader::empty();
+        let mut data_files_modified_time = vec![];
+        for path in data_files() {
+            if let Some(content) = read_file(&path) {
+                es_game_data.load(&path, &content);
+                data_files_modified_time.push((path.clone(), modified_time(&path)));
+            }
+        }
+
+        State {
+            game_data: resolve(&es_game_data),
+            current_game: None,
+            data_loader: es_game_data,
+            data_files_modified_time,
+            watch_data_files: OS::godot_singleton().is_debug_build(),
+            time_since_last_check: 0.0,
+        }
+    }
 
-        let mut data_dir = gdnative::Directory::new();
-        data_dir.open("res://data".into()).unwrap();
+    #[export]
+    fn _process(&mut self, _owner: OwnerNode, delta: f32) {
+        if !self.watch_data_files {
+            return;
+        }
+        self.time_since_last_check += delta;
+        if self.time_since_last_check >= DATA_CHECK_INTERVAL {
+            self.time_since_last_check = 0.0;
+            self.reload_changed_data_files();
+        }
+    }
 
-        data_dir.list_dir_begin(false, false).unwrap();
-        loop {
-            let path = data_dir.get_next();
-            if path.is_empty() {
-                break;
-            }
-            if !path.ends_with(&".txt".into()) {
+    /// reload data files that changed since they were loaded, unload the ones that were deleted or
+    /// renamed, load the new ones, and update the current game with them
+    pub fn reload_changed_data_files(&mut self) {
+        let mut changed = false;
+        let mut removed = vec![];
+        for (path, last_modified_time) in self.data_files_modified_time.iter_mut() {
+            let new_modified_time = modified_time(path);
+            if new_modified_time == *last_modified_time {
                 continue;
             }
-            let full_path = format!("res://data/{}", path.to_string());
-            let mut game_data_file = gdnative::File::new();
-            game_data_file.open(full_path.into(), 1).unwrap();
-            es_game_data.load(&game_data_file.get_as_text().to_string());
-            game_data_file.close();
+            changed = true;
+            if let Some(content) = read_file(path) {
+                godot_print!("reloading {}", path);
+                self.data_loader.reload(path, &content);
+                *last_modified_time = new_modified_time;
+            } else {
+                godot_print!("unloading {}", path);
+                self.data_loader.unload(path);
+                removed.push(path.clone());
+            }
         }
-        data_dir.list_dir_end();
+        self.data_files_modified_time
+            .retain(|(path, _)| !removed.contains(path));
 
-        State {
-            game_data: es_game_data.resolve(),
-            current_game: None,
+        for path in data_files() {
+            if self
+                .data_files_modified_time
+                .iter()
+                .any(|(loaded, _)| *loaded == path)
+            {
+                continue;
+            }
+            if let Some(content) = read_file(&path) {
+                godot_print!("loading {}", path);
+                self.data_loader.load(&path, &content);
+                self.data_files_modified_time
+                    .push((path.clone(), modified_time(&path)));
+                changed = true;
+            }
+        }
+        if changed {
+            self.game_data = resolve(&self.data_loader);
+            if let Some(game) = self.current_game.as_mut() {
+                self.game_data.refresh_game(game);
+            }
         }
     }
 
     /// return the current game, or a new one if there is not one already
-    pub fn current_game_or_new(&mut self) -> &crate::Game {
+    pub fn current_game_or_new(&mut self) -> Result<&crate::Game, crate::GameCreationError> {
         if self.current_game.is_none() {
-            self.new_game();
+            self.new_game()?;
         }
-        self.current_game.as_ref().unwrap()
+        Ok(self.current_game.as_ref().unwrap())
     }
 
     /// create a new game from default state
-    pub fn new_game(&mut self) {
-        self.current_game = Some(self.game_data.create_game().unwrap())
+    pub fn new_game(&mut self) -> Result<(), crate::GameCreationError> {
+        self.current_game = Some(self.game_data.create_game()?);
+        Ok(())
+    }
+
+    /// check if there is a saved game that can be loaded
+    pub fn has_saved_game(&self) -> bool {
+        gdnative::File::new().file_exists(SAVE_FILE.into())
+    }
+
+    /// save the current game to the save file
+    pub fn save_game(&self) -> Result<(), crate::SaveError> {
+        let game = match self.current_game.as_ref() {
+            Some(game) => game,
+            None => return Ok(()),
+        };
+        let content = crate::save::SavedGame::from_game(game).to_ron()?;
+
+        let mut save_file = gdnative::File::new();
+        save_file
+            .open(SAVE_FILE.into(), 2)
+            .map_err(|error| crate::SaveError::Io(format!("{:?}", error)))?;
+        save_file.store_string(content.into());
+        save_file.close();
+        Ok(())
+    }
+
+    /// replace the current game with the one from the save file
+    pub fn load_game(&mut self) -> Result<(), crate::SaveError> {
+        let mut save_file = gdnative::File::new();
+        save_file
+            .open(SAVE_FILE.into(), 1)
+            .map_err(|error| crate::SaveError::Io(format!("{:?}", error)))?;
+        let content = save_file.get_as_text().to_string();
+        save_file.close();
+
+        let saved_game = crate::save::SavedGame::from_ron(&content)?;
+        self.current_game = Some(self.game_data.load_game(&saved_game)?);
+        Ok(())
     }
 }
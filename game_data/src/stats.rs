@@ -0,0 +1,304 @@
+use super::data::{Attributes, EngineType, ShipInstance};
+use super::Space;
+
+/// heat a ship can hold for each ton of mass before overheating
+pub const HEAT_CAPACITY_PER_MASS: f32 = 100.0;
+
+/// A space or capacity of a ship, partly used by it's outfits
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Capacity {
+    /// space available, from the ship and outfits that add some
+    pub total: f32,
+    /// space used by outfits
+    pub used: f32,
+}
+
+impl Capacity {
+    pub(crate) fn new(base: f32, free: f32, added: f32) -> Self {
+        let total = base + added;
+        Capacity {
+            total,
+            used: total - free,
+        }
+    }
+
+    /// space left
+    pub fn free(&self) -> f32 {
+        self.total - self.used
+    }
+
+    /// is more space used than available
+    pub fn is_overused(&self) -> bool {
+        self.used > self.total
+    }
+}
+
+/// Stats of a ship with all it's installed outfits
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ShipStats {
+    /// it's mass, with outfits
+    pub mass: f32,
+    /// it's drag
+    pub drag: f32,
+    /// thrust of all engines
+    pub forward_thrust: f32,
+    /// reverse thrust of all engines
+    pub reverse_thrust: f32,
+    /// turning power of all engines
+    pub turn: f32,
+    /// energy that can be stored
+    pub energy_capacity: f32,
+    /// energy generated each second
+    pub energy_generation: f32,
+    /// energy consumed each second when idle
+    pub energy_consumption: f32,
+    /// energy collected from stars each second
+    pub solar_collection: f32,
+    /// energy consumed each second when thrusting
+    pub thrusting_energy: f32,
+    /// energy consumed each second when turning
+    pub turning_energy: f32,
+    /// heat generated each second when idle
+    pub heat_generation: f32,
+    /// heat generated each second when thrusting
+    pub thrusting_heat: f32,
+    /// heat removed each second by active cooling
+    pub cooling: f32,
+    /// share of the heat dissipated each second
+    pub heat_dissipation: f32,
+    /// heat the ship can hold before overheating
+    pub max_heat: f32,
+    /// shields
+    pub shields: f32,
+    /// shields regenerated each second
+    pub shield_generation: f32,
+    /// energy used to regenerate shields each second
+    pub shield_energy: f32,
+    /// hull
+    pub hull: f32,
+    /// hull repaired each second
+    pub hull_repair_rate: f32,
+    /// energy used to repair the hull each second
+    pub hull_energy: f32,
+    /// space for cargo
+    pub cargo_space: Capacity,
+    /// space for outfits
+    pub outfit_space: Capacity,
+    /// space for weapons
+    pub weapon_capacity: Capacity,
+    /// space for engines
+    pub engine_capacity: Capacity,
+    /// ports for guns
+    pub gun_ports: Capacity,
+    /// mounts for turrets
+    pub turret_mounts: Capacity,
+    /// fuel that can be stored
+    pub fuel_capacity: f32,
+    /// fuel collected from stars
+    pub ramscoop: f32,
+    /// bunks for crew and passengers
+    pub bunks: f32,
+    /// crew needed
+    pub required_crew: f32,
+    /// credits paid each day
+    pub maintenance_costs: f32,
+}
+
+impl ShipStats {
+    /// net energy produced each second when idle
+    pub fn idle_energy(&self) -> f32 {
+        self.energy_generation + self.solar_collection - self.energy_consumption
+    }
+
+    /// energy consumed each second when thrusting, turning and regenerating shields and hull
+    pub fn max_energy_usage(&self) -> f32 {
+        self.energy_consumption
+            + self.thrusting_energy
+            + self.turning_energy
+            + self.shield_energy
+            + self.hull_energy
+    }
+
+    /// bunks left for passengers once the crew is on board
+    pub fn free_bunks(&self) -> f32 {
+        self.bunks - self.required_crew
+    }
+
+    /// capacities of the ship, by space
+    pub fn capacities(&self) -> [(Space, Capacity); 6] {
+        [
+            (Space::CargoSpace, self.cargo_space),
+            (Space::OutfitSpace, self.outfit_space),
+            (Space::WeaponCapacity, self.weapon_capacity),
+            (Space::EngineCapacity, self.engine_capacity),
+            (Space::GunPorts, self.gun_ports),
+            (Space::TurretMounts, self.turret_mounts),
+        ]
+    }
+}
+
+impl ShipInstance {
+    /// compute the stats of the ship with all it's installed outfits
+    pub fn stats(&self) -> ShipStats {
+        let base = &self.model.attributes;
+        let attributes = self.get_attributes();
+        let mut added = Attributes::default();
+        for (outfit, count) in &self.outfits {
+            added.add(&outfit.attributes.positive(), *count as f32);
+        }
+        let engine_usage = |ty: EngineType| -> (f32, f32) {
+            self.get_engines(ty)
+                .fold((0.0, 0.0), |(energy, heat), (engine, count)| {
+                    (
+                        energy + engine.energy_consumption * count,
+                        heat + engine.heat_generation * count,
+                    )
+                })
+        };
+        let (thrusting_energy, thrusting_heat) = engine_usage(EngineType::Thrust);
+        let (turning_energy, _) = engine_usage(EngineType::Turn);
+        let mass = self.get_mass();
+
+        ShipStats {
+            mass,
+            drag: self.get_drag(),
+            forward_thrust: self.get_forward_thrust(),
+            reverse_thrust: self.get_reverse_thrust(),
+            turn: self.get_turn(),
+            energy_capacity: attributes.energy_capacity,
+            energy_generation: attributes.energy_generation,
+            energy_consumption: attributes.energy_consumption,
+            solar_collection: attributes.solar_collection,
+            thrusting_energy,
+            turning_energy,
+            heat_generation: attributes.heat_generation,
+            thrusting_heat,
+            cooling: attributes.cooling,
+            heat_dissipation: attributes.heat_dissipation,
+            max_heat: HEAT_CAPACITY_PER_MASS * mass,
+            shields: attributes.shields,
+            shield_generation: attributes.shield_generation,
+            shield_energy: attributes.shield_energy,
+            hull: attributes.hull,
+            hull_repair_rate: attributes.hull_repair_rate,
+            hull_energy: attributes.hull_energy,
+            cargo_space: Capacity::new(base.cargo_space, attributes.cargo_space, added.cargo_space),
+            outfit_space: Capacity::new(
+                base.outfit_space,
+                attributes.outfit_space,
+                added.outfit_space,
+            ),
+            weapon_capacity: Capacity::new(
+                base.weapon_capacity,
+                attributes.weapon_capacity,
+                added.weapon_capacity,
+            ),
+            engine_capacity: Capacity::new(
+                base.engine_capacity,
+                attributes.engine_capacity,
+                added.engine_capacity,
+            ),
+            gun_ports: Capacity::new(base.gun_ports, attributes.gun_ports, added.gun_ports),
+            turret_mounts: Capacity::new(
+                base.turret_mounts,
+                attributes.turret_mounts,
+                added.turret_mounts,
+            ),
+            fuel_capacity: attributes.fuel_capacity,
+            ramscoop: attributes.ramscoop,
+            bunks: attributes.bunks,
+            required_crew: attributes.required_crew,
+            maintenance_costs: attributes.maintenance_costs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::data::ShipInstance;
+    use crate::test_data;
+
+    const OUTFITS: &str = r#"outfit "Generator"
+	category "Power"
+	"mass" 20
+	"outfit space" -4
+	"energy generation" 2
+	"heat generation" 3
+	"energy capacity" 500
+
+outfit "Thruster"
+	category "Engines"
+	"mass" 10
+	"outfit space" -3
+	"engine capacity" -3
+	"thrust" 8
+	"thrusting energy" 0.5
+	"thrusting heat" 1.5
+
+outfit "Reverser"
+	category "Engines"
+	"mass" 5
+	"outfit space" -2
+	"engine capacity" -2
+	"reverse thrust" 4
+
+outfit "Cargo Expansion"
+	category "Systems"
+	"outfit space" -3
+	"cargo space" 15
+
+outfit "Blaster"
+	category "Guns"
+	"mass" 3
+	"outfit space" -3
+	"weapon capacity" -3
+	"gun ports" -1
+"#;
+
+    #[test]
+    fn can_compute_ship_stats() {
+        let data = test_data::data_from(&[test_data::SHIPS, OUTFITS]);
+        let model = Arc::new(data.get_ship("Shuttle").unwrap().clone());
+        let mut ship = ShipInstance::new("Shuttle", model);
+        for (name, count) in &[
+            ("Generator", 1),
+            ("Thruster", 2),
+            ("Reverser", 1),
+            ("Cargo Expansion", 1),
+            ("Blaster", 1),
+        ] {
+            ship.outfits
+                .push((data.get_outfit(name).unwrap().clone(), *count));
+        }
+        let stats = ship.stats();
+
+        assert_eq!(stats.mass, 70.0 + 20.0 + 20.0 + 2.0 * 10.0 + 5.0 + 3.0);
+        assert_eq!(stats.forward_thrust, 16.0);
+        assert_eq!(stats.reverse_thrust, 4.0);
+        assert_eq!(stats.energy_capacity, 500.0);
+        assert_eq!(stats.idle_energy(), 2.0);
+        assert_eq!(stats.thrusting_energy, 1.0);
+        assert_eq!(stats.thrusting_heat, 3.0);
+        assert_eq!(stats.heat_generation, 3.0);
+        assert_eq!(stats.max_heat, 100.0 * stats.mass);
+        assert_eq!(stats.hull, 600.0);
+        assert_eq!(stats.fuel_capacity, 400.0);
+        assert_eq!(stats.free_bunks(), 1.0);
+
+        assert_eq!(stats.cargo_space.total, 35.0);
+        assert_eq!(stats.cargo_space.used, 0.0);
+        assert_eq!(stats.outfit_space.total, 20.0);
+        assert_eq!(stats.outfit_space.used, 4.0 + 2.0 * 3.0 + 2.0 + 3.0 + 3.0);
+        assert_eq!(stats.outfit_space.free(), 2.0);
+        assert_eq!(stats.engine_capacity.used, 8.0);
+        assert_eq!(stats.weapon_capacity.free(), 3.0);
+        assert_eq!(stats.gun_ports.total, 2.0);
+        assert_eq!(stats.gun_ports.used, 1.0);
+        assert!(stats
+            .capacities()
+            .iter()
+            .all(|(_, capacity)| !capacity.is_overused()));
+    }
+}
@@ -0,0 +1,89 @@
+use super::loader::UnresolvedESGameLoader;
+use super::{ESGameLoader, Game};
+
+/// the ship of the player, with it's hyperdrive
+pub const SHIPS: &str = r#"outfit "Hyperdrive"
+	category "Systems"
+	"mass" 20
+	"hyperdrive" 1
+
+ship "Shuttle"
+	sprite "ship/shuttle"
+	thumbnail "thumbnail/shuttle"
+	attributes
+		category "Transport"
+		"cost" 100000
+		"hull" 600
+		"mass" 70
+		"drag" 1.7
+		"heat dissipation" .8
+		"fuel capacity" 400
+		"cargo space" 20
+		"bunks" 2
+		"required crew" 1
+		"outfit space" 20
+		"weapon capacity" 6
+		"engine capacity" 20
+		weapon
+			"blast radius" 12
+			"shield damage" 120
+			"hull damage" 60
+			"hit force" 180
+	outfits
+		"Hyperdrive"
+	engine -9 38
+	gun 0 -30
+	gun 4 -30
+	explode "tiny explosion" 10
+	description "A shuttle."
+"#;
+
+/// the planet the player starts on, selling what the tests put in it's shipyard and outfitter
+pub const PLANET: &str = r#"planet "Home"
+	description "A planet."
+	spaceport "A spaceport."
+	shipyard "Common Ships"
+	outfitter "Common Outfits"
+"#;
+
+/// the system the player starts in, to which tests can add links, trades and objects
+pub const SYSTEM: &str = r#"system "Home"
+	pos 0 0
+	government Republic
+	habitable 100
+"#;
+
+/// the start of the game, landed on "Home" with a shuttle
+pub const START: &str = r#"start
+	system "Home"
+	planet "Home"
+	date 16 11 3013
+	set "license: Pilot's"
+	ship "Shuttle"
+	account
+		credits 10000
+		score 400
+		mortgage Mortgage
+			principal 0
+			interest 0.004
+			term 365
+"#;
+
+/// Create a game from the shared data and the extra data of a test
+pub fn game_with(extra: &str) -> Game {
+    game_from(&[SHIPS, PLANET, SYSTEM, START, extra])
+}
+
+/// Create a game from data files, for tests replacing some of the shared data
+pub fn game_from(files: &[&str]) -> Game {
+    data_from(files).create_game().unwrap()
+}
+
+/// Load and resolve data files, for tests that also load saved games
+pub fn data_from(files: &[&str]) -> ESGameLoader {
+    let mut loader = UnresolvedESGameLoader::empty();
+    for (index, data) in files.iter().enumerate() {
+        loader.load(&format!("data{}.txt", index), data);
+    }
+    loader.resolve()
+}
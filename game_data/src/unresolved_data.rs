@@ -4,11 +4,46 @@ pub struct Ship {
     /// it's name
     pub name: String,
     /// it's sprite
-    pub sprite: String,
+    pub sprite: super::data::Sprite,
     /// it's outfits
     pub outfits: Vec<(String, u32)>,
     /// it's drag
     pub drag: f32,
     /// it's mass
-    pub mass: u32,
+    pub mass: f32,
+    /// it's attributes, without outfits
+    pub attributes: super::data::Attributes,
+    /// price of the ship without it's outfits, in credits
+    pub cost: u32,
+    /// licenses needed to buy it
+    pub licenses: Vec<String>,
+}
+
+/// A planet
+#[derive(Debug, Clone)]
+pub struct Planet {
+    /// it's name
+    pub name: String,
+    /// name of it's government, if different from the one of it's system
+    pub government: Option<String>,
+    /// it's attributes
+    pub attributes: Vec<String>,
+    /// names of the lists of ships sold in it's shipyard
+    pub shipyard: Vec<String>,
+    /// names of the lists of outfits sold in it's outfitter
+    pub outfitter: Vec<String>,
+    /// it's description
+    pub description: String,
+    /// description of it's spaceport
+    pub spaceport: String,
+    /// image shown when landed
+    pub landscape: Option<String>,
+    /// share of the value of the fleet asked as a bribe to land
+    pub bribe: f32,
+    /// probability that the fleet is scanned when landing
+    pub security: f32,
+    /// reputation needed to land, if any
+    pub required_reputation: Option<f32>,
+    /// tribute it can pay to the player, if any
+    pub tribute: Option<super::data::Tribute>,
 }
@@ -97,7 +97,7 @@ impl Menu {
         let state = state_instance.into_script();
         state
             .map(|state| {
-                if state.current_game.is_none() {
+                if state.current_game.is_none() && !state.has_saved_game() {
                     unsafe {
                         owner
                             .get_node("Menu/GameControls/Continue".into())
@@ -151,12 +151,34 @@ impl Menu {
 
     #[export]
     fn _continue_game(&mut self, owner: OwnerNode) {
-        unsafe {
-            owner
-                .get_tree()
-                .expect("was able to get tree from node")
-                .change_scene("res://game/Game.tscn".into())
-                .expect("was able to change scene");
+        let state_node = unsafe { owner.get_node("/root/State".into()) };
+        let state_instance: Instance<game_data::State> =
+            unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
+        let state = state_instance.into_script();
+        let loaded_game = state
+            .map_mut(|state| {
+                if state.current_game.is_none() {
+                    state.load_game()
+                } else {
+                    Ok(())
+                }
+            })
+            .unwrap();
+        match loaded_game {
+            Ok(()) => unsafe {
+                owner
+                    .get_tree()
+                    .expect("was able to get tree from node")
+                    .change_scene("res://game/Game.tscn".into())
+                    .expect("was able to change scene");
+            },
+            Err(error) => unsafe {
+                owner
+                    .get_node("Menu/Error".into())
+                    .and_then(|node| node.cast::<Label>())
+                    .expect("Label Error is present")
+                    .set_text(format!("Could not load saved game: {}", error).into());
+            },
         }
     }
 
@@ -166,13 +188,22 @@ impl Menu {
         let state_instance: Instance<game_data::State> =
             unsafe { Instance::try_from_unsafe_base(state_node.unwrap()).unwrap() };
         let state = state_instance.into_script();
-        state.map_mut(|state| state.current_game = None).unwrap();
-        unsafe {
-            owner
-                .get_tree()
-                .expect("was able to get tree from node")
-                .change_scene("res://game/Game.tscn".into())
-                .expect("was able to change scene");
+        let new_game = state.map_mut(|state| state.new_game()).unwrap();
+        match new_game {
+            Ok(()) => unsafe {
+                owner
+                    .get_tree()
+                    .expect("was able to get tree from node")
+                    .change_scene("res://game/Game.tscn".into())
+                    .expect("was able to change scene");
+            },
+            Err(error) => unsafe {
+                owner
+                    .get_node("Menu/Error".into())
+                    .and_then(|node| node.cast::<Label>())
+                    .expect("Label Error is present")
+                    .set_text(format!("Could not start a new game: {}", error).into());
+            },
         }
     }
 }